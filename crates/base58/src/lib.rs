@@ -1,38 +1,56 @@
-use hasher::{double_sha256, sha256};
+use hasher::double_sha256;
 use num_bigint::BigUint;
 use num_traits::{ToPrimitive, Zero};
 
-const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+pub const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
 /// Decode a base58 string into an vector of bytes
 pub fn decode_base58(base58: &str) -> Result<Vec<u8>, String> {
-    // Step 1: Decode Base58 string to a big integer
+    if base58.is_empty() {
+        return Err("Invalid Base58 string: empty string".to_string());
+    }
+
+    // Step 1: Validate every character before doing any arithmetic
+    let mut digits = Vec::with_capacity(base58.len());
+    for (position, char) in base58.chars().enumerate() {
+        let char_index = ALPHABET.find(char).ok_or_else(|| {
+            format!(
+                "Invalid character '{}' at position {} in Base58 string",
+                char, position
+            )
+        })?;
+        digits.push(char_index as u32);
+    }
+
+    // Step 2: Decode Base58 digits to a big integer
     let mut num = BigUint::zero();
     let base = BigUint::from(58u32);
-
-    for char in base58.chars() {
-        let char_index = ALPHABET
-            .find(char)
-            .ok_or_else(|| format!("Invalid character '{}' in Base58 string", char))?;
-        num = num * &base + BigUint::from(char_index as u32);
+    for digit in digits.iter() {
+        num = num * &base + BigUint::from(*digit);
     }
 
-    // Step 2: Convert the integer to bytes
-    let byte_array = num.to_bytes_be();
+    // Step 3: Convert the integer to bytes; zero has no significant bytes,
+    // otherwise a string of only '1's would get an extra zero byte
+    let byte_array = if num.is_zero() {
+        vec![]
+    } else {
+        num.to_bytes_be()
+    };
 
-    // Step 3: Add leading zero bytes for each '1' in the Base58 string
-    let leading_zeros = base58.chars().take_while(|&c| c == '1').count();
+    // Step 4: Add leading zero bytes for each '1' in the Base58 string
+    let leading_zeros = digits.iter().take_while(|&&d| d == 0).count();
     let mut full_byte_array = vec![0u8; leading_zeros];
     full_byte_array.extend_from_slice(&byte_array);
 
-    // Step 4: Extract and verify checksum
+    // Step 5: Extract and verify checksum
     if full_byte_array.len() < 4 {
         return Err("Invalid Base58 string: too short to contain a checksum".to_string());
     }
 
-    // Step 5: calculate checksum and compare with given checksum
+    // Step 6: calculate checksum and compare with given checksum
     let (data, checksum) = full_byte_array.split_at(full_byte_array.len() - 4);
-    let calc_checksum = double_sha256(data).unwrap();
+    let calc_checksum =
+        double_sha256(data).map_err(|e| format!("Failed to compute checksum: {:?}", e))?;
 
     if checksum != &calc_checksum[..4] {
         return Err(format!(
@@ -41,7 +59,7 @@ pub fn decode_base58(base58: &str) -> Result<Vec<u8>, String> {
         ));
     }
 
-    // Step 5: Return decoded data
+    // Step 7: Return decoded data
     Ok(data.to_vec())
 }
 
@@ -69,7 +87,7 @@ pub fn encode_base58(bytes: Vec<u8>) -> Result<String, String> {
             .to_u32()
             .ok_or("Failed to convert BigUint to u32")? as usize;
         num /= &base;
-        data.insert(0, ALPHABET.chars().nth(rem).unwrap());
+        data.insert(0, ALPHABET.as_bytes()[rem] as char);
     }
 
     // Add Base58 '1's for each leading zero byte
@@ -83,8 +101,8 @@ pub fn encode_base58(bytes: Vec<u8>) -> Result<String, String> {
 
 /// Encode bytes to base58check format
 pub fn encode_base58check(bytes: &[u8]) -> Result<String, String> {
-    // create the checksum
-    let hash = sha256(bytes).unwrap();
+    // create the checksum with the first 4 bytes of hash256
+    let hash = double_sha256(bytes).unwrap();
     let checksum = &hash[..4].to_vec();
 
     let mut data = Vec::with_capacity(bytes.len() + checksum.len()) as Vec<u8>;
@@ -93,3 +111,12 @@ pub fn encode_base58check(bytes: &[u8]) -> Result<String, String> {
 
     encode_base58(data).map_err(|e| format!("Encoding failed: {}", e))
 }
+
+/// Prepend a version byte to the payload and encode it to base58check
+/// format, as done for addresses and WIF private keys
+pub fn encode_base58check_versioned(version: u8, payload: &[u8]) -> Result<String, String> {
+    let mut data = Vec::with_capacity(payload.len() + 1);
+    data.push(version);
+    data.extend_from_slice(payload);
+    encode_base58check(&data)
+}
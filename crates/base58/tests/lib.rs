@@ -1,4 +1,4 @@
-use base58::{decode_base58, encode_base58, encode_base58check};
+use base58::{decode_base58, encode_base58, encode_base58check, encode_base58check_versioned};
 use num_bigint::BigUint;
 use num_traits::Num;
 
@@ -57,9 +57,9 @@ mod test {
         ];
 
         let expected = [
-            "wdA2ffYs5cudrdkhFm5Ym94AuLvavacapuDBL2CAcvqXHcM56",
-            "Qwj1mwXNifQmo5VV2s587usAy4QRUviQsBxoe4EJXyb5CAhV",
-            "2WhRyzK3iKFveq4hvQ3VR9uau26t6qZCMhADPAVMeMR6S5dV2q",
+            "wdA2ffYs5cudrdkhFm5Ym94AuLvavacapuDBL2CAcvqYPkcvi",
+            "Qwj1mwXNifQmo5VV2s587usAy4QRUviQsBxoe4EJXyWz4GBs",
+            "2WhRyzK3iKFveq4hvQ3VR9uau26t6qZCMhADPAVMeMR6VraBbX",
         ];
 
         for i in 0..hexs.len() {
@@ -69,4 +69,95 @@ mod test {
             assert_eq!(result, expected[i]);
         }
     }
+
+    #[test]
+    fn test_encode_with_checksum_known_answer() {
+        // The checksum is the first 4 bytes of hash256, so these match
+        // the well known address and uncompressed WIF of the key 1
+        let mut address = vec![0x00u8];
+        address.extend([
+            0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3,
+            0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6,
+        ]);
+        assert_eq!(
+            encode_base58check(&address).unwrap(),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+
+        let mut wif = vec![0x80u8];
+        wif.extend([0u8; 31]);
+        wif.push(0x01);
+        assert_eq!(
+            encode_base58check(&wif).unwrap(),
+            "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf"
+        );
+    }
+
+    #[test]
+    fn test_encode_versioned_p2pkh() {
+        // hash160 of the compressed SEC of 1*G
+        let h160 = [
+            0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3,
+            0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6,
+        ];
+        assert_eq!(
+            encode_base58check_versioned(0x00, &h160).unwrap(),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            encode_base58check_versioned(0x6f, &h160).unwrap(),
+            "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+        );
+    }
+
+    #[test]
+    fn test_encode_versioned_wif() {
+        // private key 1, compressed, mainnet
+        let mut payload = vec![0u8; 32];
+        payload[31] = 1;
+        payload.push(0x01);
+        let wif = encode_base58check_versioned(0x80, &payload).unwrap();
+        assert_eq!(wif, "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn");
+
+        let mut expected = vec![0x80u8];
+        expected.extend_from_slice(&payload);
+        assert_eq!(decode_base58(&wif).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_decode_empty_fail() {
+        assert!(decode_base58("").is_err());
+    }
+
+    #[test]
+    fn test_decode_only_ones() {
+        // all '1's decode to zero bytes only, which cannot hold a checksum
+        for ones in ["1", "111", "1111"] {
+            assert!(decode_base58(ones).is_err());
+        }
+        // a zero payload byte followed by a zero checksum is invalid
+        assert!(decode_base58("11111").is_err());
+    }
+
+    #[test]
+    fn test_decode_only_ones_encoded() {
+        // a zero-byte payload round-trips through base58check
+        let encoded = encode_base58check(&[0u8, 0u8]).unwrap();
+        assert!(encoded.starts_with("11"));
+        assert_eq!(decode_base58(&encoded).unwrap(), vec![0u8, 0u8]);
+    }
+
+    #[test]
+    fn test_decode_invalid_characters_fail() {
+        let valid = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH";
+        for position in [0, 10, valid.len() - 1] {
+            for invalid in ['0', 'O', 'I', 'l', '+', 'é'] {
+                let mut chars: Vec<char> = valid.chars().collect();
+                chars[position] = invalid;
+                let s: String = chars.into_iter().collect();
+                let err = decode_base58(&s).unwrap_err();
+                assert!(err.contains(&format!("position {}", position)));
+            }
+        }
+    }
 }
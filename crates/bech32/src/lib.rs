@@ -0,0 +1,231 @@
+/*
+ * Bech32 encoding of segregated witness addresses
+ * See BIP173: https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+ * and BIP350 for bech32m: https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+ */
+const CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+pub const MAINNET_HRP: &str = "bc";
+pub const TESTNET_HRP: &str = "tb";
+
+/// Maximum length of a bech32 string, including the separator and checksum
+const MAX_LENGTH: usize = 90;
+
+/// Checksum flavour: bech32 for witness v0, bech32m (BIP350) for v1+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Variant {
+    /// The value the polymod of a valid checksum must be equal to
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+/// Compute the BCH checksum over a sequence of 5 bit values
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ (*value as u32);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+/// Expand the human readable part into values for checksum computation
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut result: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    result.push(0u8);
+    result.extend(hrp.bytes().map(|b| b & 31));
+    result
+}
+
+/// Compute the 6 checksum values given the human readable part and data
+fn create_checksum(hrp: &str, data: &[u8], variant: Variant) -> Vec<u8> {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+    let polymod = polymod(&values) ^ variant.constant();
+    (0..6)
+        .map(|i| ((polymod >> (5 * (5 - i))) & 31) as u8)
+        .collect()
+}
+
+/// Regroup a slice of `from` bits values into `to` bits values
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut result = Vec::new();
+    let maxv = (1u32 << to) - 1;
+
+    for value in data {
+        let v = *value as u32;
+        if (v >> from) != 0 {
+            return Err(format!("Invalid value {} for {} bits", v, from));
+        }
+        acc = (acc << from) | v;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & maxv) as u8);
+        }
+    } else if bits >= from || ((acc << (to - bits)) & maxv) != 0 {
+        return Err("Invalid padding in bits conversion".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Encode a human readable part and 5 bit values to a bech32 string
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, String> {
+    if hrp.is_empty() {
+        return Err("Human readable part cannot be empty".to_string());
+    }
+
+    let mut result = String::from(hrp);
+    result.push('1');
+
+    let checksum = create_checksum(hrp, data, variant);
+    for value in data.iter().chain(checksum.iter()) {
+        let c = CHARSET
+            .chars()
+            .nth(*value as usize)
+            .ok_or_else(|| format!("Invalid 5 bit value {}", value))?;
+        result.push(c);
+    }
+    Ok(result)
+}
+
+/// Decode a bech32 or bech32m string into its lowercase human readable
+/// part, the 5 bit data values (without checksum) and the checksum variant
+pub fn decode(bech: &str) -> Result<(String, Vec<u8>, Variant), String> {
+    if bech.len() > MAX_LENGTH {
+        return Err(format!("Bech32 string too long: {}", bech.len()));
+    }
+    if let Some(c) = bech.chars().find(|c| !('!'..='~').contains(c)) {
+        return Err(format!("Invalid character {:?} in bech32 string", c));
+    }
+
+    // BIP173: either all lowercase or all uppercase
+    let lower = bech.to_lowercase();
+    if lower != bech && bech.to_uppercase() != bech {
+        return Err("Mixed case bech32 string".to_string());
+    }
+
+    let separator = lower.rfind('1').ok_or("Missing separator '1'")?;
+    if separator == 0 {
+        return Err("Human readable part cannot be empty".to_string());
+    }
+    if separator + 7 > lower.len() {
+        return Err("Checksum too short".to_string());
+    }
+
+    let (hrp, rest) = lower.split_at(separator);
+    let data = rest[1..]
+        .chars()
+        .map(|c| {
+            CHARSET
+                .find(c)
+                .map(|i| i as u8)
+                .ok_or_else(|| format!("Invalid data character {:?}", c))
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    let variant = match polymod(&values) {
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err("Invalid checksum".to_string()),
+    };
+
+    Ok((hrp.to_string(), data[..data.len() - 6].to_vec(), variant))
+}
+
+/// Check the witness version and program length rules of BIP141
+fn check_witness_program(version: u8, program: &[u8]) -> Result<(), String> {
+    if version > 16 {
+        return Err(format!("Invalid witness version {}", version));
+    }
+
+    if program.len() < 2 || program.len() > 40 {
+        return Err(format!("Invalid witness program length {}", program.len()));
+    }
+
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(format!(
+            "Invalid witness v0 program length {}",
+            program.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Checksum variant required by a witness version (BIP350)
+fn segwit_variant(version: u8) -> Variant {
+    if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    }
+}
+
+/// Encode a witness program to a segwit address, using bech32
+/// for witness v0 and bech32m for v1 and later
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String, String> {
+    check_witness_program(version, program)?;
+
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+    encode(hrp, &data, segwit_variant(version))
+}
+
+/// Decode a segwit address for the expected human readable part
+/// into its witness version and program
+pub fn decode_segwit_address(hrp: &str, addr: &str) -> Result<(u8, Vec<u8>), String> {
+    let (decoded_hrp, data, variant) = decode(addr)?;
+    if decoded_hrp != hrp {
+        return Err(format!(
+            "Expected human readable part {}, got {}",
+            hrp, decoded_hrp
+        ));
+    }
+
+    let (version, program) = data.split_first().ok_or("Empty data section")?;
+    let program = convert_bits(program, 5, 8, false)?;
+    check_witness_program(*version, &program)?;
+
+    if variant != segwit_variant(*version) {
+        return Err(format!(
+            "Invalid checksum variant {:?} for witness version {}",
+            variant, version
+        ));
+    }
+    Ok((*version, program))
+}
+
+/// Encode a taproot output key (x coordinate only, BIP341)
+/// to a witness v1 address (P2TR)
+pub fn taproot_address(output_key_x: &[u8; 32], testnet: bool) -> Result<String, String> {
+    let hrp = if testnet { TESTNET_HRP } else { MAINNET_HRP };
+    encode_segwit_address(hrp, 1, output_key_x)
+}
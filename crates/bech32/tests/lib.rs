@@ -0,0 +1,200 @@
+use bech32::{
+    convert_bits, decode, decode_segwit_address, encode, encode_segwit_address, taproot_address,
+    Variant, MAINNET_HRP, TESTNET_HRP,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_bits_round_trip() {
+        let data = [0u8, 1u8, 127u8, 128u8, 255u8];
+        let five = convert_bits(&data, 8, 5, true).unwrap();
+        let eight = convert_bits(&five, 5, 8, false).unwrap();
+        assert_eq!(eight, data);
+    }
+
+    #[test]
+    fn test_encode_segwit_address_p2wpkh() {
+        // BIP173 example
+        let program = [
+            117u8, 30u8, 118u8, 232u8, 25u8, 145u8, 150u8, 212u8, 84u8, 148u8, 28u8, 69u8, 209u8,
+            179u8, 163u8, 35u8, 241u8, 67u8, 59u8, 214u8,
+        ];
+
+        let mainnet = encode_segwit_address(MAINNET_HRP, 0, &program).unwrap();
+        let testnet = encode_segwit_address(TESTNET_HRP, 0, &program).unwrap();
+        assert_eq!(mainnet, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+        assert_eq!(testnet, "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx");
+    }
+
+    #[test]
+    fn test_encode_segwit_address_fail_invalid_version() {
+        let program = [0u8; 20];
+        assert!(encode_segwit_address(MAINNET_HRP, 17, &program).is_err());
+    }
+
+    #[test]
+    fn test_decode_bech32m_valid() {
+        // BIP350 valid bech32m strings
+        let valid = [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+            "?1v759aa",
+        ];
+        for bech in valid {
+            let (hrp, data, variant) = decode(bech).unwrap();
+            assert_eq!(variant, Variant::Bech32m);
+            assert_eq!(encode(&hrp, &data, variant).unwrap(), bech.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_decode_bech32m_invalid() {
+        // BIP350 invalid bech32m strings
+        let invalid = [
+            "\x201xj0phk",
+            "\x7f1g6xzxy",
+            "\u{80}1vctc34",
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+            "qyrz8wqd2c9m",
+            "1qyrz8wqd2c9m",
+            "y1b0jsk6g",
+            "lt1igcx5c0",
+            "in1muywd",
+            "mm1crxm3i",
+            "au1s5cgom",
+            "M1VUXWEZ",
+            "16plkw9",
+            "1p2gdwpf",
+        ];
+        for bech in invalid {
+            assert!(decode(bech).is_err(), "{:?} should be invalid", bech);
+        }
+    }
+
+    #[test]
+    fn test_decode_fail_mixed_case() {
+        assert!(decode("A1lqfn3a").is_err());
+        assert!(decode("tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq").is_err());
+    }
+
+    #[test]
+    fn test_decode_segwit_address_valid() {
+        // BIP350 valid segwit addresses
+        let valid = [
+            (
+                MAINNET_HRP,
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                TESTNET_HRP,
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                MAINNET_HRP,
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                1,
+                "751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (MAINNET_HRP, "BC1SW50QGDZ25J", 16, "751e"),
+            (
+                MAINNET_HRP,
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                2,
+                "751e76e8199196d454941c45d1b3a323",
+            ),
+            (
+                TESTNET_HRP,
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                0,
+                "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+            (
+                TESTNET_HRP,
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                1,
+                "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+        ];
+        for (hrp, addr, version, program) in valid {
+            let (decoded_version, decoded_program) = decode_segwit_address(hrp, addr).unwrap();
+            assert_eq!(decoded_version, version);
+            assert_eq!(hex::encode(&decoded_program), program);
+            assert_eq!(
+                encode_segwit_address(hrp, version, &decoded_program).unwrap(),
+                addr.to_lowercase()
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_segwit_address_invalid() {
+        // BIP350 invalid segwit addresses
+        let invalid = [
+            // invalid human readable part
+            "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+            // bech32 instead of bech32m
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+            "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+            "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+            // bech32m instead of bech32
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+            "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+            // invalid character in checksum
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+            // invalid witness version
+            "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+            // invalid program lengths
+            "bc1pw5dgrnzv",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+            "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+            // mixed case
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+            // invalid padding
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+            // empty data section
+            "bc1gmk9yu",
+        ];
+        for addr in invalid {
+            let hrp = if addr.to_lowercase().starts_with("tb") {
+                TESTNET_HRP
+            } else {
+                MAINNET_HRP
+            };
+            assert!(
+                decode_segwit_address(hrp, addr).is_err(),
+                "{} should be invalid",
+                addr
+            );
+        }
+    }
+
+    #[test]
+    fn test_taproot_address() {
+        // BIP350 vector whose output key is the x coordinate of G
+        let output_key: [u8; 32] =
+            hex::decode("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        assert_eq!(
+            taproot_address(&output_key, false).unwrap(),
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+        );
+        let testnet = taproot_address(&output_key, true).unwrap();
+        assert_eq!(
+            decode_segwit_address(TESTNET_HRP, &testnet).unwrap(),
+            (1, output_key.to_vec())
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::Read;
+
+/// Read a given number of bytes from a reader
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    reader
+        .read_exact(&mut buf)
+        .map_err(|e| format!("Failed to read {} bytes: {}", N, e))?;
+    Ok(buf)
+}
+
+/// Read a 4 bytes little-endian unsigned integer
+pub fn read_u32_le(reader: &mut impl Read) -> Result<u32, String> {
+    read_bytes::<4>(reader).map(u32::from_le_bytes)
+}
+
+/// Read a 8 bytes little-endian unsigned integer
+pub fn read_u64_le(reader: &mut impl Read) -> Result<u64, String> {
+    read_bytes::<8>(reader).map(u64::from_le_bytes)
+}
+
+/// Read a 8 bytes little-endian signed integer
+pub fn read_i64_le(reader: &mut impl Read) -> Result<i64, String> {
+    read_bytes::<8>(reader).map(i64::from_le_bytes)
+}
+
+/// Serialize an unsigned integer as 4 bytes little-endian
+pub fn write_u32_le(n: u32) -> Vec<u8> {
+    n.to_le_bytes().to_vec()
+}
+
+/// Serialize an unsigned integer as 8 bytes little-endian
+pub fn write_u64_le(n: u64) -> Vec<u8> {
+    n.to_le_bytes().to_vec()
+}
+
+/// Read a variable length integer (varint) as in Programming Bitcoin chapter 5
+pub fn read_varint(reader: &mut impl Read) -> Result<u64, String> {
+    let prefix = read_bytes::<1>(reader)?[0];
+    match prefix {
+        // 0xfd means the next two bytes are the number
+        0xfd => read_bytes::<2>(reader).map(|b| u16::from_le_bytes(b) as u64),
+        // 0xfe means the next four bytes are the number
+        0xfe => read_u32_le(reader).map(|n| n as u64),
+        // 0xff means the next eight bytes are the number
+        0xff => read_u64_le(reader),
+        // anything else is just the integer
+        _ => Ok(prefix as u64),
+    }
+}
+
+/// Encode an integer as a variable length integer (varint)
+pub fn write_varint(n: u64) -> Vec<u8> {
+    if n < 0xfd {
+        vec![n as u8]
+    } else if n <= 0xffff {
+        let mut result = vec![0xfdu8];
+        result.extend_from_slice(&(n as u16).to_le_bytes());
+        result
+    } else if n <= 0xffffffff {
+        let mut result = vec![0xfeu8];
+        result.extend(write_u32_le(n as u32));
+        result
+    } else {
+        let mut result = vec![0xffu8];
+        result.extend(write_u64_le(n));
+        result
+    }
+}
+
+/// Read a byte string prefixed by its length as a varint
+pub fn read_varstr(reader: &mut impl Read) -> Result<Vec<u8>, String> {
+    let len = read_varint(reader)?;
+    let mut result = Vec::new();
+    reader
+        .take(len)
+        .read_to_end(&mut result)
+        .map_err(|e| format!("Failed to read {} bytes: {}", len, e))?;
+
+    if result.len() as u64 != len {
+        return Err(format!(
+            "Expected {} bytes but only {} were available",
+            len,
+            result.len()
+        ));
+    }
+    Ok(result)
+}
+
+/// Serialize a byte string prefixed by its length as a varint
+pub fn write_varstr(bytes: &[u8]) -> Vec<u8> {
+    let mut result = write_varint(bytes.len() as u64);
+    result.extend_from_slice(bytes);
+    result
+}
+
+/// Encode bytes as a lowercase hexadecimal string
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decode a hexadecimal string (either case) into bytes
+pub fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.len().is_multiple_of(2) {
+        return Err(format!("Odd length hexadecimal string: {}", s.len()));
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16);
+            let low = (pair[1] as char).to_digit(16);
+            match (high, low) {
+                (Some(h), Some(l)) => Ok((h * 16 + l) as u8),
+                _ => Err(format!(
+                    "Invalid hexadecimal digits: {}",
+                    String::from_utf8_lossy(pair)
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Decode a hexadecimal string that must represent exactly 32 bytes
+pub fn from_hex_32(s: &str) -> Result<[u8; 32], String> {
+    let bytes = from_hex(s)?;
+    <[u8; 32]>::try_from(bytes.as_slice())
+        .map_err(|_| format!("Expected 32 bytes, got {}", bytes.len()))
+}
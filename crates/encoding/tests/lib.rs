@@ -0,0 +1,124 @@
+use encoding::{
+    from_hex, from_hex_32, read_i64_le, read_u32_le, read_u64_le, read_varint, read_varstr, to_hex,
+    write_u32_le, write_u64_le, write_varint, write_varstr,
+};
+use std::io::Cursor;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u32_le_round_trip() {
+        for n in [0u32, 1u32, 0xfffffffeu32, u32::MAX] {
+            let bytes = write_u32_le(n);
+            assert_eq!(bytes.len(), 4);
+            let mut cursor = Cursor::new(bytes);
+            assert_eq!(read_u32_le(&mut cursor).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn test_u32_le_byte_order() {
+        assert_eq!(write_u32_le(1u32), vec![1u8, 0u8, 0u8, 0u8]);
+    }
+
+    #[test]
+    fn test_u64_le_round_trip() {
+        for n in [0u64, 1u64, u32::MAX as u64 + 1, u64::MAX] {
+            let bytes = write_u64_le(n);
+            assert_eq!(bytes.len(), 8);
+            let mut cursor = Cursor::new(bytes);
+            assert_eq!(read_u64_le(&mut cursor).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn test_i64_le() {
+        for n in [0i64, -1i64, i64::MIN, i64::MAX] {
+            let mut cursor = Cursor::new(n.to_le_bytes().to_vec());
+            assert_eq!(read_i64_le(&mut cursor).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn test_read_u32_le_fail_too_short() {
+        let mut cursor = Cursor::new(vec![1u8, 0u8, 0u8]);
+        assert!(read_u32_le(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_varint_round_trip() {
+        let cases: [(u64, usize); 7] = [
+            (0, 1),
+            (0xfc, 1),
+            (0xfd, 3),
+            (0xffff, 3),
+            (0x10000, 5),
+            (0xffffffff, 5),
+            (0x100000000, 9),
+        ];
+
+        for (n, len) in cases {
+            let bytes = write_varint(n);
+            assert_eq!(bytes.len(), len);
+            let mut cursor = Cursor::new(bytes);
+            assert_eq!(read_varint(&mut cursor).unwrap(), n);
+        }
+    }
+
+    #[test]
+    fn test_varstr_empty() {
+        let bytes = write_varstr(&[]);
+        assert_eq!(bytes, vec![0u8]);
+
+        let mut cursor = Cursor::new(bytes);
+        assert_eq!(read_varstr(&mut cursor).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_varstr() {
+        let user_agent = b"/programmingbitcoin:0.1/";
+        let bytes = write_varstr(user_agent);
+        assert_eq!(bytes[0], 24u8);
+        assert_eq!(&bytes[1..], user_agent);
+
+        let mut cursor = Cursor::new(bytes);
+        assert_eq!(read_varstr(&mut cursor).unwrap(), user_agent.to_vec());
+    }
+
+    #[test]
+    fn test_read_varstr_fail_too_short() {
+        let mut cursor = Cursor::new(vec![3u8, 1u8, 2u8]);
+        assert!(read_varstr(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let bytes = vec![0x00, 0x01, 0xab, 0xff];
+        assert_eq!(to_hex(&bytes), "0001abff");
+        assert_eq!(from_hex("0001abff").unwrap(), bytes);
+        assert_eq!(from_hex("0001ABFF").unwrap(), bytes);
+        assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_from_hex_fail() {
+        // odd length
+        assert!(from_hex("abc").is_err());
+        // non-hex characters, including a sign accepted by from_str_radix
+        assert!(from_hex("zz").is_err());
+        assert!(from_hex("+1").is_err());
+        assert!(from_hex("é1").is_err());
+    }
+
+    #[test]
+    fn test_from_hex_32() {
+        let hex = "0000000000000000000000000000000000000000000000000000000000000001";
+        let bytes = from_hex_32(hex).unwrap();
+        assert_eq!(bytes[31], 1);
+        assert!(bytes[..31].iter().all(|b| *b == 0));
+        assert!(from_hex_32("00").is_err());
+        assert!(from_hex_32(&format!("{}00", hex)).is_err());
+    }
+}
@@ -3,25 +3,41 @@
  * in a field F_prime
  * See "Constructing a finite field in python"
  */
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 use num_bigint::{BigInt, BigUint};
 use num_traits::{Num, One, Zero};
-use std::cmp::Ordering;
-use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
 
+/// The prime is shared behind an `Arc`: arithmetic results reuse the
+/// modulus of their operands instead of cloning it, and `from_biguint`
+/// builds more elements of a field from an already shared one
 #[derive(Debug, Clone)]
 pub struct FieldElement {
     pub num: BigUint,
-    pub prime: BigUint,
+    prime: Arc<BigUint>,
 }
 
 /// This implementation represents a single finite field element.
 impl FieldElement {
     #[allow(dead_code)]
     pub fn new(num: &str, prime: &str) -> Result<Self, String> {
-        let bignum = BigUint::from_str_radix(num, 16).expect("Invalid number");
+        let bignum = BigUint::from_str_radix(num, 16)
+            .map_err(|e| format!("Invalid number '{}': {}", num, e))?;
 
-        let bigprime = BigUint::from_str_radix(prime, 16).expect("Invalid prime");
+        let bigprime = BigUint::from_str_radix(prime, 16)
+            .map_err(|e| format!("Invalid prime '{}': {}", prime, e))?;
 
         match bignum.cmp(&bigprime) {
             Ordering::Greater => {
@@ -34,11 +50,74 @@ impl FieldElement {
             }
             Ordering::Less => Ok(Self {
                 num: bignum,
-                prime: bigprime,
+                prime: Arc::new(bigprime),
             }),
         }
     }
 
+    /// Create a FieldElement from a big-endian unsigned integer,
+    /// checking it lies in the field [0, prime)
+    pub fn from_bytes_be(bytes: &[u8], prime: &BigUint) -> Result<Self, String> {
+        let num = BigUint::from_bytes_be(bytes);
+        if &num >= prime {
+            return Err(format!("{} isnt in the field [0..{})", num, prime));
+        }
+        Ok(Self {
+            num,
+            prime: Arc::new(prime.clone()),
+        })
+    }
+
+    /// Create a FieldElement in the field of an already shared prime,
+    /// checking it lies in the field [0, prime)
+    pub fn from_biguint(num: BigUint, prime: &Arc<BigUint>) -> Result<Self, String> {
+        if &num >= prime.as_ref() {
+            return Err(format!("{} isnt in the field [0..{})", num, prime));
+        }
+        Ok(Self {
+            num,
+            prime: prime.clone(),
+        })
+    }
+
+    /// The prime of the field
+    pub fn prime(&self) -> &BigUint {
+        &self.prime
+    }
+
+    /// The shared prime, to build other elements of the same field
+    /// with `from_biguint` without allocating a new modulus
+    pub fn shared_prime(&self) -> &Arc<BigUint> {
+        &self.prime
+    }
+
+    /// Check both elements belong to the same field, comparing the
+    /// shared pointers before the values
+    fn same_field(&self, other: &FieldElement) -> bool {
+        Arc::ptr_eq(&self.prime, &other.prime) || self.prime == other.prime
+    }
+
+    /// Big-endian bytes of `num`, without leading zeros
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        self.num.to_bytes_be()
+    }
+
+    /// Big-endian bytes of `num` left-padded with zeros to 32 bytes,
+    /// as used by SEC. Panics if `num` does not fit in 32 bytes
+    pub fn to_32_bytes_be(&self) -> [u8; 32] {
+        let bytes = self.num.to_bytes_be();
+        assert!(bytes.len() <= 32, "Field element does not fit in 32 bytes");
+        let mut result = [0u8; 32];
+        result[(32 - bytes.len())..].copy_from_slice(&bytes);
+        result
+    }
+
+    /// Hex of `num` left-padded with zeros to 64 characters, the way
+    /// 256 bits values are shown in the book and by Bitcoin Core
+    pub fn to_hex(&self) -> String {
+        format!("{:064x}", self.num)
+    }
+
     #[allow(dead_code)]
     fn wrap_exponent(&self, exponent: &BigInt) -> BigUint {
         let zero = BigInt::zero();
@@ -47,7 +126,7 @@ impl FieldElement {
         match exponent.cmp(&zero) {
             Ordering::Less => {
                 let pos_exp = (-exponent).to_biguint().unwrap();
-                &self.prime - &one - &pos_exp
+                &*self.prime - &one - &pos_exp
             }
             Ordering::Equal => exponent.to_biguint().unwrap(),
             Ordering::Greater => exponent.to_biguint().unwrap(),
@@ -68,9 +147,9 @@ impl FieldElement {
         let mut exp_copy = exp.clone();
         while exp_copy > BigUint::zero() {
             if &exp_copy % BigUint::from(2u32) == BigUint::one() {
-                result = (&result * &base) % &self.prime;
+                result = (&result * &base) % &*self.prime;
             }
-            base = (&base * &base) % &self.prime;
+            base = (&base * &base) % &*self.prime;
             exp_copy /= BigUint::from(2u32);
         }
 
@@ -80,14 +159,153 @@ impl FieldElement {
         }
     }
 
-    pub fn sqrt(&self) -> Self {
+    /// Exponentiation by a small exponent, with the squares and cubes
+    /// of point arithmetic computed by plain multiplications
+    pub fn pow_u32(&self, exponent: u32) -> Self {
+        match exponent {
+            0 => Self {
+                num: BigUint::one(),
+                prime: self.prime.clone(),
+            },
+            1 => self.clone(),
+            2 => self * self,
+            3 => &(self * self) * self,
+            _ => Self {
+                num: self.num.modpow(&BigUint::from(exponent), &self.prime),
+                prime: self.prime.clone(),
+            },
+        }
+    }
+
+    /// Exponentiation by a nonnegative exponent, skipping the
+    /// negative exponent handling of `pow`
+    pub fn pow_uint(&self, exponent: &BigUint) -> Self {
+        Self {
+            num: self.num.modpow(exponent, &self.prime),
+            prime: self.prime.clone(),
+        }
+    }
+
+    /// Former name of `pow_uint`
+    #[deprecated(note = "renamed to `pow_uint`")]
+    pub fn pow_u(&self, exponent: &BigUint) -> Self {
+        self.pow_uint(exponent)
+    }
+
+    /// Compute `self * self`, cheaper than `pow` with exponent 2
+    pub fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Check if the representative in [0, prime) is even
+    pub fn is_even(&self) -> bool {
+        !self.num.bit(0)
+    }
+
+    /// Check if the representative in [0, prime) is odd
+    pub fn is_odd(&self) -> bool {
+        self.num.bit(0)
+    }
+
+    /// Multiplicative inverse with Fermat's little theorem,
+    /// `num^(prime - 2)`. Zero has no inverse
+    pub fn inverse(&self) -> Result<FieldElement, String> {
+        if self.num.is_zero() {
+            return Err("Zero has no inverse in a finite field".to_string());
+        }
+        let exp = &*self.prime - BigUint::from(2u32);
+        Ok(self.pow_uint(&exp))
+    }
+
+    /// Modular addition, failing instead of panicking when the
+    /// elements are from different fields
+    pub fn checked_add(&self, other: &FieldElement) -> Result<FieldElement, String> {
+        if !self.same_field(other) {
+            return Err("Cannot add elements from different fields".to_string());
+        }
+
+        Ok(FieldElement {
+            num: (&self.num + &other.num) % &*self.prime,
+            prime: self.prime.clone(),
+        })
+    }
+
+    /// Modular subtraction, failing instead of panicking when the
+    /// elements are from different fields
+    pub fn checked_sub(&self, other: &FieldElement) -> Result<FieldElement, String> {
+        if !self.same_field(other) {
+            return Err("Cannot subtract elements from different fields".to_string());
+        }
+
+        let num = if self.num < other.num {
+            // Wrap around if b > a
+            (&self.num + &*self.prime - &other.num) % &*self.prime
+        } else {
+            (&self.num - &other.num) % &*self.prime
+        };
+
+        Ok(FieldElement {
+            num,
+            prime: self.prime.clone(),
+        })
+    }
+
+    /// Modular multiplication, failing instead of panicking when the
+    /// elements are from different fields
+    pub fn checked_mul(&self, other: &FieldElement) -> Result<FieldElement, String> {
+        if !self.same_field(other) {
+            return Err("Cannot multiply elements from different fields".to_string());
+        }
+
+        Ok(FieldElement {
+            num: (&self.num * &other.num) % &*self.prime,
+            prime: self.prime.clone(),
+        })
+    }
+
+    /// Modular division, failing instead of panicking when the
+    /// elements are from different fields or when dividing by zero
+    pub fn checked_div(&self, other: &FieldElement) -> Result<FieldElement, String> {
+        if !self.same_field(other) {
+            return Err("Cannot divide elements from different fields".to_string());
+        }
+
+        let inv = other
+            .inverse()
+            .map_err(|_| "Cannot divide by zero in a finite field".to_string())?;
+        self.checked_mul(&inv)
+    }
+
+    /// Square root with the `num^((prime + 1) / 4)` shortcut, valid for
+    /// primes equal to 3 mod 4 like the secp256k1 one; other primes are an
+    /// error. The candidate is squared back, so non-residues give an error
+    /// instead of a bogus root
+    pub fn sqrt(&self) -> Result<FieldElement, String> {
         let one = BigUint::one();
         let four = BigUint::from(4u32);
-        let exp = (&self.prime + &one) / &four;
-        let res = self.num.modpow(&exp, &self.prime);
-        FieldElement {
-            num: res,
-            prime: self.prime.clone(),
+        if &*self.prime % &four != BigUint::from(3u32) {
+            return Err(format!(
+                "sqrt is only supported for primes equal to 3 mod 4, not {}",
+                self.prime
+            ));
+        }
+        let exp = (&*self.prime + &one) / &four;
+        let candidate = self.pow_uint(&exp);
+        if &candidate.square() != self {
+            return Err("no square root".to_string());
+        }
+        Ok(candidate)
+    }
+
+    /// Both square roots, ordered as `(even, odd)`. For zero both roots
+    /// are zero
+    pub fn sqrt_both(&self) -> Result<(FieldElement, FieldElement), String> {
+        let root = self.sqrt()?;
+        let other = -&root;
+        if root.is_even() {
+            Ok((root, other))
+        } else {
+            Ok((other, root))
         }
     }
 }
@@ -101,6 +319,18 @@ impl fmt::Display for FieldElement {
     }
 }
 
+/// Same layout as `Display` with `num` and `prime` in hex, easier to
+/// read for 256 bits values. `{:#x}` prefixes both with `0x`
+impl fmt::LowerHex for FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "FiniteElement_{:#x}({:#x})", *self.prime, self.num)
+        } else {
+            write!(f, "FiniteElement_{:x}({:x})", *self.prime, self.num)
+        }
+    }
+}
+
 /// Implement PartialEq trait to mimic __eq__ in python
 impl PartialEq for FieldElement {
     /// Check if two implementations of FieldElement are equal.
@@ -109,7 +339,18 @@ impl PartialEq for FieldElement {
     /// required because Rust automatically provides !=
     /// when you implement the PartialEq trait
     fn eq(&self, other: &Self) -> bool {
-        self.num == other.num && self.prime == other.prime
+        self.num == other.num && self.same_field(other)
+    }
+}
+
+impl Eq for FieldElement {}
+
+/// Hash both `num` and `prime`, consistent with `PartialEq`, so field
+/// elements can be used as keys of a `HashMap` or a `HashSet`
+impl Hash for FieldElement {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num.hash(state);
+        self.prime.hash(state);
     }
 }
 
@@ -118,19 +359,9 @@ impl Add for FieldElement {
     type Output = Self;
 
     /// We have to ensure that the elements are from the same
-    /// finite field and define it with the modulo operation,
-    /// returning an instance of FiniteElement struct
+    /// finite field, panicking otherwise. See `checked_add`
     fn add(self, other: FieldElement) -> Self {
-        match self.prime.cmp(&other.prime) {
-            Ordering::Equal => {
-                let bignum = (&self.num + &other.num) % &self.prime;
-                Self {
-                    num: bignum,
-                    prime: self.prime.clone(),
-                }
-            }
-            _ => panic!("Cannot add two numbers in different fields"),
-        }
+        self.checked_add(&other).unwrap()
     }
 }
 
@@ -138,18 +369,8 @@ impl Add for FieldElement {
 impl<'b> Add<&'b FieldElement> for &FieldElement {
     type Output = FieldElement;
 
-    /// Modular addition for references
     fn add(self, other: &'b FieldElement) -> FieldElement {
-        if self.prime != other.prime {
-            panic!("Cannot add elements from different fields");
-        }
-
-        let result = (&self.num + &other.num) % &self.prime;
-
-        FieldElement {
-            num: result,
-            prime: self.prime.clone(),
-        }
+        self.checked_add(other).unwrap()
     }
 }
 
@@ -158,21 +379,7 @@ impl Sub for FieldElement {
     type Output = Self;
 
     fn sub(self, other: FieldElement) -> Self {
-        if self.prime != other.prime {
-            panic!("Cannot subtract numbers from different fields");
-        }
-
-        let result = if self.num < other.num {
-            // Wrap around if b > a
-            (&self.num + &self.prime - &other.num) % &self.prime
-        } else {
-            (&self.num - &other.num) % &self.prime
-        };
-
-        Self {
-            num: result,
-            prime: self.prime.clone(),
-        }
+        self.checked_sub(&other).unwrap()
     }
 }
 
@@ -181,20 +388,7 @@ impl<'b> Sub<&'b FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn sub(self, other: &'b FieldElement) -> FieldElement {
-        if self.prime != other.prime {
-            panic!("Cannot subtract elements from different fields");
-        }
-
-        let result = if self.num < other.num {
-            (&self.num + &self.prime - &other.num) % &self.prime
-        } else {
-            (&self.num - &other.num) % &self.prime
-        };
-
-        FieldElement {
-            num: result,
-            prime: self.prime.clone(),
-        }
+        self.checked_sub(other).unwrap()
     }
 }
 
@@ -203,19 +397,9 @@ impl Mul for FieldElement {
     type Output = Self;
 
     /// We have to ensure that the elements are from the same
-    /// finite field and define it with the modulo operation,
-    /// returning an instance of FiniteElement struct
+    /// finite field, panicking otherwise. See `checked_mul`
     fn mul(self, other: FieldElement) -> Self {
-        match self.prime.cmp(&other.prime) {
-            Ordering::Equal => {
-                let bignum = (&self.num * &other.num) % &self.prime;
-                Self {
-                    num: bignum,
-                    prime: self.prime.clone(),
-                }
-            }
-            _ => panic!("Cannot multiple 2 numbers in different fields"),
-        }
+        self.checked_mul(&other).unwrap()
     }
 }
 
@@ -223,18 +407,8 @@ impl Mul for FieldElement {
 impl<'b> Mul<&'b FieldElement> for &FieldElement {
     type Output = FieldElement;
 
-    /// Modular multiplication for references
     fn mul(self, other: &'b FieldElement) -> FieldElement {
-        if self.prime != other.prime {
-            panic!("Cannot multiply elements from different fields");
-        }
-
-        let bignum = (&self.num * &other.num) % &self.prime;
-
-        FieldElement {
-            num: bignum,
-            prime: self.prime.clone(),
-        }
+        self.checked_mul(other).unwrap()
     }
 }
 
@@ -243,23 +417,7 @@ impl Div for FieldElement {
     type Output = Self;
 
     fn div(self, other: FieldElement) -> Self {
-        if self.prime != other.prime {
-            panic!("Cannot divide numbers from different fields");
-        }
-        if other.num.is_zero() {
-            panic!("Cannot divide by zero in a finite field");
-        }
-
-        // Compute modular inverse of `other.num` using Extended Euclidean Algorithm
-        let inv = other
-            .num
-            .modpow(&(self.prime.clone() - BigUint::from(2u32)), &self.prime);
-        let result = (&self.num * inv) % &self.prime;
-
-        Self {
-            num: result,
-            prime: self.prime.clone(),
-        }
+        self.checked_div(&other).unwrap()
     }
 }
 
@@ -268,25 +426,39 @@ impl<'b> Div<&'b FieldElement> for &FieldElement {
     type Output = FieldElement;
 
     fn div(self, other: &'b FieldElement) -> FieldElement {
-        if self.prime != other.prime {
-            panic!("Cannot divide elements from different fields");
-        }
-
-        if other.num.is_zero() {
-            panic!("Cannot divide by zero in a finite field");
-        }
-
-        // Compute modular inverse of `other.num`
-        let inv = other
-            .num
-            .modpow(&(self.prime.clone() - BigUint::from(2u32)), &self.prime);
+        self.checked_div(other).unwrap()
+    }
+}
 
-        // Perform modular multiplication
-        let result = (&self.num * &inv) % &self.prime;
+/// Additive inverse, `prime - num`, in the same field
+impl Neg for &FieldElement {
+    type Output = FieldElement;
 
+    fn neg(self) -> FieldElement {
         FieldElement {
-            num: result,
+            num: (&*self.prime - &self.num) % &*self.prime,
             prime: self.prime.clone(),
         }
     }
 }
+
+/// Accumulate, as in `acc += &term`. Panics like `Add`, see `checked_add`
+impl AddAssign<&FieldElement> for FieldElement {
+    fn add_assign(&mut self, other: &FieldElement) {
+        *self = self.checked_add(other).unwrap();
+    }
+}
+
+/// Subtract, as in `acc -= &term`. Panics like `Sub`, see `checked_sub`
+impl SubAssign<&FieldElement> for FieldElement {
+    fn sub_assign(&mut self, other: &FieldElement) {
+        *self = self.checked_sub(other).unwrap();
+    }
+}
+
+/// Multiply, as in `acc *= &term`. Panics like `Mul`, see `checked_mul`
+impl MulAssign<&FieldElement> for FieldElement {
+    fn mul_assign(&mut self, other: &FieldElement) {
+        *self = self.checked_mul(other).unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use field_element::FieldElement;
-use num_bigint::BigInt;
+use num_bigint::{BigInt, BigUint};
 use num_traits::Num;
 
 #[cfg(test)]
@@ -402,7 +402,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(fe_1.sqrt(), fe_expected);
+        assert_eq!(fe_1.sqrt().unwrap(), fe_expected);
     }
 
     #[test]
@@ -412,11 +412,389 @@ mod tests {
             "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
         )
         .unwrap();
+        // -1 is not a square mod a prime equal to 3 mod 4, the
+        // `(prime + 1) / 4` shortcut alone would answer 1
+        assert!(fe_1.sqrt().is_err());
+    }
+
+    #[test]
+    fn test_sqrt_non_residue() {
+        // squares mod 7 are 0, 1, 2 and 4
+        for num in ["0", "1", "2", "4"] {
+            let fe = FieldElement::new(num, "7").unwrap();
+            assert_eq!(fe.sqrt().unwrap().square(), fe);
+        }
+        for num in ["3", "5", "6"] {
+            let fe = FieldElement::new(num, "7").unwrap();
+            assert!(fe.sqrt().is_err());
+        }
+    }
+
+    #[test]
+    fn test_sqrt_prime_one_mod_four() {
+        // 13 is 1 mod 4, where the shortcut does not apply: 4 = 2^2 is a
+        // residue, so the error must not claim there is no square root
+        let fe = FieldElement::new("4", "d").unwrap();
+        let err = fe.sqrt().unwrap_err();
+        assert!(err.contains("3 mod 4"));
+        assert!(fe.sqrt_both().is_err());
+    }
+
+    #[test]
+    fn test_sqrt_both() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        let x = FieldElement::new(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            prime,
+        )
+        .unwrap();
+        let seven = FieldElement::new("7", prime).unwrap();
+        let c = x.pow_u32(3) + seven;
+
+        let (even, odd) = c.sqrt_both().unwrap();
+        assert!(even.is_even());
+        assert!(odd.is_odd());
+        assert_eq!(even.square(), c);
+        assert_eq!(odd.square(), c);
+
+        // the even root is the generator's y coordinate
+        let g_y = FieldElement::new(
+            "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+            prime,
+        )
+        .unwrap();
+        assert_eq!(even, g_y);
+
+        let non_residue = FieldElement::new("3", "7").unwrap();
+        assert!(non_residue.sqrt_both().is_err());
+    }
+
+    #[test]
+    fn test_pow_uint() {
+        let fe_1 = FieldElement::new(
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        )
+        .unwrap();
         let fe_expected = FieldElement::new(
+            "0000000000000000000000000000000000000000000000000000000000000008",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        )
+        .unwrap();
+
+        assert_eq!(fe_1.pow_uint(&BigUint::from(3u32)), fe_expected);
+        #[allow(deprecated)]
+        let old_name = fe_1.pow_u(&BigUint::from(3u32));
+        assert_eq!(old_name, fe_expected);
+    }
+
+    #[test]
+    fn test_pow_uint_equals_pow() {
+        let fe_1 = FieldElement::new(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        )
+        .unwrap();
+
+        for exponent in ["0", "1", "2", "3", "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"] {
+            let signed = BigInt::from_str_radix(exponent, 16).unwrap();
+            let unsigned = BigUint::from_str_radix(exponent, 16).unwrap();
+            assert_eq!(fe_1.pow_uint(&unsigned), fe_1.pow(&signed));
+        }
+    }
+
+    #[test]
+    fn test_create_field_element_fail_invalid_num() {
+        let fe = FieldElement::new(
+            "not a hex number",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        );
+        assert!(fe.is_err());
+    }
+
+    #[test]
+    fn test_create_field_element_fail_invalid_prime() {
+        let fe = FieldElement::new(
             "0000000000000000000000000000000000000000000000000000000000000001",
+            "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        );
+        assert!(fe.is_err());
+    }
+
+    #[test]
+    fn test_is_even_is_odd() {
+        for (num, even) in [
+            ("0", true),
+            ("1", false),
+            ("2", true),
+            ("a", true),
+            ("b", false),
+        ] {
+            let fe = FieldElement::new(num, "d").unwrap();
+            assert_eq!(fe.is_even(), even);
+            assert_eq!(fe.is_odd(), !even);
+        }
+    }
+
+    #[test]
+    fn test_square() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        for num in [
+            "0",
+            "1",
+            "2",
+            "7",
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        ] {
+            let fe = FieldElement::new(num, prime).unwrap();
+            assert_eq!(fe.square(), fe.pow(&BigInt::from(2u32)));
+        }
+    }
+
+    #[test]
+    fn test_pow_u32() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        for num in [
+            "0",
+            "2",
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        ] {
+            let fe = FieldElement::new(num, prime).unwrap();
+            for exp in [0u32, 1, 2, 3, 4, 17, 1000] {
+                assert_eq!(fe.pow_u32(exp), fe.pow(&BigInt::from(exp)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_bytes_be() {
+        let prime = BigUint::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let mut bytes = [0u8; 32];
+        bytes[31] = 7;
+        let fe = FieldElement::from_bytes_be(&bytes, &prime).unwrap();
+        assert_eq!(fe.num, BigUint::from(7u32));
+        assert_eq!(fe.prime(), &prime);
+
+        // the prime itself and anything above are out of the field
+        assert!(FieldElement::from_bytes_be(&prime.to_bytes_be(), &prime).is_err());
+        assert!(FieldElement::from_bytes_be(&[0xff; 32], &prime).is_err());
+    }
+
+    #[test]
+    fn test_to_bytes_be() {
+        let prime = BigUint::from_str_radix(
             "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
         )
         .unwrap();
-        assert_eq!(fe_1.sqrt(), fe_expected);
+        let mut bytes = [0u8; 32];
+        bytes[30] = 0x01;
+        bytes[31] = 0x02;
+        let fe = FieldElement::from_bytes_be(&bytes, &prime).unwrap();
+        assert_eq!(fe.to_bytes_be(), vec![0x01, 0x02]);
+        assert_eq!(fe.to_32_bytes_be(), bytes);
+
+        let full = [0xabu8; 32];
+        let fe = FieldElement::from_bytes_be(&full, &prime).unwrap();
+        assert_eq!(fe.to_bytes_be(), full.to_vec());
+        assert_eq!(fe.to_32_bytes_be(), full);
+    }
+
+    #[test]
+    fn test_lower_hex() {
+        let fe = FieldElement::new(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        )
+        .unwrap();
+        assert_eq!(
+            format!("{:x}", fe),
+            "FiniteElement_fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f\
+             (79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798)"
+        );
+
+        let small = FieldElement::new("a", "d").unwrap();
+        assert_eq!(format!("{:x}", small), "FiniteElement_d(a)");
+        assert_eq!(format!("{:#x}", small), "FiniteElement_0xd(0xa)");
+        assert_eq!(format!("{}", small), "FiniteElement_13(10)");
+    }
+
+    #[test]
+    fn test_inverse() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        let one = FieldElement::new("1", prime).unwrap();
+        for num in [
+            "1",
+            "2",
+            "7",
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2E",
+        ] {
+            let fe = FieldElement::new(num, prime).unwrap();
+            let inv = fe.inverse().unwrap();
+            assert_eq!(&fe * &inv, one);
+            assert_eq!(&one / &fe, inv);
+        }
+    }
+
+    #[test]
+    fn test_inverse_zero_fail() {
+        let fe = FieldElement::new("0", "d").unwrap();
+        assert!(fe.inverse().is_err());
+    }
+
+    #[test]
+    fn test_hash_set() {
+        let mut set = std::collections::HashSet::new();
+        for num in ["1", "2", "3", "1"] {
+            set.insert(FieldElement::new(num, "d").unwrap());
+        }
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&FieldElement::new("2", "d").unwrap()));
+        assert!(!set.contains(&FieldElement::new("4", "d").unwrap()));
+
+        // same num in another field is another element
+        assert!(!set.contains(&FieldElement::new("2", "11").unwrap()));
+        set.insert(FieldElement::new("2", "11").unwrap());
+        assert_eq!(set.len(), 4);
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let step = FieldElement::new("5", "d").unwrap();
+        let mut acc = FieldElement::new("0", "d").unwrap();
+        for _ in 0..10 {
+            acc += &step;
+        }
+        // 50 mod 13
+        assert_eq!(acc, FieldElement::new("b", "d").unwrap());
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let step = FieldElement::new("5", "d").unwrap();
+        let mut acc = FieldElement::new("3", "d").unwrap();
+        acc -= &step;
+        // 3 - 5 mod 13
+        assert_eq!(acc, FieldElement::new("b", "d").unwrap());
+        acc -= &step;
+        assert_eq!(acc, FieldElement::new("6", "d").unwrap());
+    }
+
+    #[test]
+    fn test_mul_assign() {
+        let base = FieldElement::new("3", "d").unwrap();
+        let mut acc = FieldElement::new("1", "d").unwrap();
+        for _ in 0..5 {
+            acc *= &base;
+        }
+        // 3^5 = 243 mod 13
+        assert_eq!(acc, FieldElement::new("9", "d").unwrap());
+        assert_eq!(acc, base.pow_u32(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot add elements from different fields")]
+    fn test_add_assign_different_fields_fail() {
+        let mut acc = FieldElement::new("1", "d").unwrap();
+        acc += &FieldElement::new("1", "11").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot subtract elements from different fields")]
+    fn test_sub_assign_different_fields_fail() {
+        let mut acc = FieldElement::new("1", "d").unwrap();
+        acc -= &FieldElement::new("1", "11").unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot multiply elements from different fields")]
+    fn test_mul_assign_different_fields_fail() {
+        let mut acc = FieldElement::new("1", "d").unwrap();
+        acc *= &FieldElement::new("1", "11").unwrap();
+    }
+
+    #[test]
+    fn test_checked_ops() {
+        let a = FieldElement::new("7", "d").unwrap();
+        let b = FieldElement::new("c", "d").unwrap();
+        assert_eq!(a.checked_add(&b).unwrap(), &a + &b);
+        assert_eq!(a.checked_sub(&b).unwrap(), &a - &b);
+        assert_eq!(a.checked_mul(&b).unwrap(), &a * &b);
+        assert_eq!(a.checked_div(&b).unwrap(), &a / &b);
+    }
+
+    #[test]
+    fn test_checked_ops_different_fields_fail() {
+        let a = FieldElement::new("7", "d").unwrap();
+        let b = FieldElement::new("7", "11").unwrap();
+        assert!(a.checked_add(&b).is_err());
+        assert!(a.checked_sub(&b).is_err());
+        assert!(a.checked_mul(&b).is_err());
+        assert!(a.checked_div(&b).is_err());
+    }
+
+    #[test]
+    fn test_checked_div_by_zero_fail() {
+        let a = FieldElement::new("7", "d").unwrap();
+        let zero = FieldElement::new("0", "d").unwrap();
+        assert!(a.checked_div(&zero).is_err());
+    }
+
+    #[test]
+    fn test_to_hex() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        let gx = FieldElement::new(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            prime,
+        )
+        .unwrap();
+        assert_eq!(
+            gx.to_hex(),
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+
+        let seven = FieldElement::new("7", prime).unwrap();
+        assert_eq!(
+            seven.to_hex(),
+            "0000000000000000000000000000000000000000000000000000000000000007"
+        );
+    }
+
+    #[test]
+    fn test_shared_prime() {
+        let prime = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
+        let a = FieldElement::new("2", prime).unwrap();
+        let b = FieldElement::new("3", prime).unwrap();
+        assert_eq!(a.prime(), &BigUint::from_str_radix(prime, 16).unwrap());
+
+        // independently built elements of the same field compare by value
+        assert_eq!(a.prime(), b.prime());
+        let c = FieldElement::from_bytes_be(&[5u8], a.prime()).unwrap();
+        assert_eq!(&a + &b, c);
+
+        // elements derived from `a` share its modulus
+        let d = FieldElement::from_biguint(BigUint::from(5u32), a.shared_prime()).unwrap();
+        assert!(std::sync::Arc::ptr_eq(a.shared_prime(), d.shared_prime()));
+        assert!(std::sync::Arc::ptr_eq(
+            a.shared_prime(),
+            (&a * &b).shared_prime()
+        ));
+        assert_eq!(d, c);
+
+        assert!(FieldElement::from_biguint(a.prime().clone(), a.shared_prime()).is_err());
+    }
+
+    #[test]
+    fn test_neg() {
+        let a = FieldElement::new("3", "d").unwrap();
+        assert_eq!(-&a, FieldElement::new("a", "d").unwrap());
+        assert_eq!(&a + &(-&a), FieldElement::new("0", "d").unwrap());
+        let zero = FieldElement::new("0", "d").unwrap();
+        assert_eq!(-&zero, zero);
     }
 }
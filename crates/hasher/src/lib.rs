@@ -1,10 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+use core::array::TryFromSliceError;
 use hmac::{Hmac, Mac};
 use ripemd::Ripemd160;
 use sha2::{Digest, Sha256, Sha512};
-use std::array::TryFromSliceError;
 
 pub const MAINNET_PREFIX: u8 = 0u8; // 0x00
 pub const TESTNET_PREFIX: u8 = 111u8; // 0x6F
+pub const MAINNET_P2SH_PREFIX: u8 = 5u8; // 0x05
+pub const TESTNET_P2SH_PREFIX: u8 = 196u8; // 0xC4
+pub const MAINNET_WIF_PREFIX: u8 = 128u8; // 0x80
+pub const TESTNET_WIF_PREFIX: u8 = 239u8; // 0xEF
 
 /// Alias for HMAC-SHA256
 type HmacSha256 = Hmac<Sha256>;
@@ -31,15 +40,15 @@ pub fn double_sha256(message: &[u8]) -> Result<[u8; 32], TryFromSliceError> {
 }
 
 /// Apply ripemd160 hash to a given slice of bytes
-pub fn ripemd160(message: &[u8]) -> Result<[u8; 20], std::array::TryFromSliceError> {
+pub fn ripemd160(message: &[u8]) -> Result<[u8; 20], TryFromSliceError> {
     let mut hasher = Ripemd160::new();
     hasher.update(message);
     <[u8; 20]>::try_from(hasher.finalize().as_slice())
 }
 
-/// Apply hash160 hash to a given slice of bytes
+/// Apply hash160 hash (sha256 followed by ripemd160) to a given slice of bytes
 pub fn hash160(message: &[u8]) -> Result<[u8; 20], TryFromSliceError> {
-    let first_hash = double_sha256(message)?;
+    let first_hash = sha256(message)?;
 
     // First hash
     let slice_hash = first_hash.as_slice();
@@ -48,6 +57,19 @@ pub fn hash160(message: &[u8]) -> Result<[u8; 20], TryFromSliceError> {
     ripemd160(slice_hash)
 }
 
+/// Apply the BIP340 tagged hash, sha256(sha256(tag) || sha256(tag) || data),
+/// to the concatenation of the given slices of bytes
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> Result<[u8; 32], TryFromSliceError> {
+    let tag_hash = sha256(tag.as_bytes())?;
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for part in data {
+        hasher.update(part);
+    }
+    <[u8; 32]>::try_from(hasher.finalize().as_slice())
+}
+
 /// Update some key with data to convert it in a secure result
 /// Mainly used in deterministic usage of Digital Signature Algorithm
 /// and Elliptc Curve Digital Signature Algorithm
@@ -61,6 +83,11 @@ pub fn hmac256(key: &[u8], data: &[&[u8]]) -> Result<Vec<u8>, String> {
     Ok(mac.finalize().into_bytes().to_vec())
 }
 
+/// Same as `hmac256` for a single slice of data
+pub fn hmac256_one(key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    hmac256(key, &[data])
+}
+
 /// Update some key with data to convert it in a secure result
 ///
 /// Mainly used in derivation of paths as specified in BIP32
@@ -73,3 +100,8 @@ pub fn hmac512(key: &[u8], data: &[&[u8]]) -> Result<Vec<u8>, String> {
     }
     Ok(mac.finalize().into_bytes().to_vec())
 }
+
+/// Same as `hmac512` for a single slice of data
+pub fn hmac512_one(key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    hmac512(key, &[data])
+}
@@ -0,0 +1,57 @@
+use hasher::{hash160, hmac256, hmac256_one, hmac512, hmac512_one};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_hash160() {
+        // ripemd160(sha256(m)), a single sha256 before ripemd160
+        assert_eq!(
+            to_hex(&hash160(b"").unwrap()),
+            "b472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+        );
+
+        // compressed SEC of 1*G, the hash of the key 1 P2PKH address
+        let sec = [
+            0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce,
+            0x87, 0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81,
+            0x5b, 0x16, 0xf8, 0x17, 0x98,
+        ];
+        assert_eq!(
+            to_hex(&hash160(&sec).unwrap()),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn test_hmac256_one() {
+        let key = b"key";
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let one = hmac256_one(key, data).unwrap();
+        assert_eq!(
+            to_hex(&one),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+        assert_eq!(one, hmac256(key, &[data]).unwrap());
+        assert_eq!(one, hmac256(key, &[&data[..9], &data[9..]]).unwrap());
+    }
+
+    #[test]
+    fn test_hmac512_one() {
+        let key = b"key";
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let one = hmac512_one(key, data).unwrap();
+        assert_eq!(
+            to_hex(&one),
+            "b42af09057bac1e2d41708e48a902e09b5ff7f12ab428a4fe86653c73dd248fb\
+             82f948a549f7b791a5b41915ee4d1ec3935357e4e2317250d0372afa2ebeeb3a"
+        );
+        assert_eq!(one, hmac512(key, &[data]).unwrap());
+        assert_eq!(one, hmac512(key, &[&data[..9], &[], &data[9..]]).unwrap());
+    }
+}
@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use key::Key;
+use num_bigint::BigUint;
+use num_traits::Num;
+use secp256k1::Secp256k1Point;
+
+/// Fixed inputs, so runs on different revisions stay comparable
+const SCALAR: &str = "3b78ce563f89a0ed9414f5aa28ad0d96d6795f9c63e7f4c2c8b1b8e1a6d4f2a1";
+const POINT_SCALAR: u64 = 0xdeadbeef;
+const PRIVATE_KEY: u64 = 0x1234_5678_9abc_def0;
+const MESSAGE: [u8; 32] = [0x42; 32];
+
+fn scalar() -> BigUint {
+    BigUint::from_str_radix(SCALAR, 16).unwrap()
+}
+
+fn bench_mul_generator(c: &mut Criterion) {
+    let g = Secp256k1Point::generator();
+    let k = scalar();
+    c.bench_function("k*G", |b| b.iter(|| black_box(g) * black_box(&k)));
+}
+
+fn bench_mul_point(c: &mut Criterion) {
+    let p = Secp256k1Point::generator().mul_u64(POINT_SCALAR);
+    let k = scalar();
+    c.bench_function("k*P", |b| b.iter(|| black_box(&p) * black_box(&k)));
+}
+
+fn bench_sign(c: &mut Criterion) {
+    let key = Key::from_biguint(BigUint::from(PRIVATE_KEY)).unwrap();
+    c.bench_function("sign", |b| b.iter(|| key.sign(black_box(MESSAGE)).unwrap()));
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let key = Key::from_biguint(BigUint::from(PRIVATE_KEY)).unwrap();
+    let signature = key.sign(MESSAGE).unwrap();
+    c.bench_function("verify", |b| {
+        b.iter(|| key.verify(black_box(&MESSAGE), black_box(&signature)))
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_mul_generator,
+    bench_mul_point,
+    bench_sign,
+    bench_verify
+);
+criterion_main!(benches);
@@ -1,41 +1,93 @@
-use base58::encode_base58check;
-use field_element::FieldElement;
-use hasher::{hash160, hmac256, MAINNET_PREFIX, TESTNET_PREFIX};
+#[cfg(feature = "rand")]
+use base58::ALPHABET;
+use base58::{decode_base58, encode_base58check_versioned};
+use bech32::{encode_segwit_address, MAINNET_HRP, TESTNET_HRP};
+use encoding::{from_hex_32, to_hex};
+use hasher::{
+    hash160, hmac256, hmac256_one, tagged_hash, MAINNET_P2SH_PREFIX, MAINNET_PREFIX,
+    MAINNET_WIF_PREFIX, TESTNET_P2SH_PREFIX, TESTNET_PREFIX, TESTNET_WIF_PREFIX,
+};
 use num_bigint::BigUint;
-use num_traits::One;
-use secp256k1::{Secp256k1, Secp256k1Point};
+use num_traits::{One, Zero};
+use secp256k1::{mod_inverse_order, Secp256k1, Secp256k1Point};
+use std::fmt;
+
+/// Bitcoin network a key or an address belongs to.
+/// Regtest and signet share the testnet version bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Regtest,
+    Signet,
+}
+
+impl Network {
+    /// Check if the network uses the testnet version bytes
+    pub fn is_testnet(&self) -> bool {
+        !matches!(self, Network::Mainnet)
+    }
+
+    /// Version byte of WIF encoded private keys
+    pub fn wif_prefix(&self) -> u8 {
+        if self.is_testnet() {
+            TESTNET_WIF_PREFIX
+        } else {
+            MAINNET_WIF_PREFIX
+        }
+    }
+}
+
+/// Base58 encoded address types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    P2pkh,
+    P2sh,
+}
+
+/// Information extracted from a valid base58 address. Addresses
+/// of every test network are reported as `Network::Testnet`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressInfo {
+    pub network: Network,
+    pub address_type: AddressType,
+    pub hash: [u8; 20],
+}
+
+/// Every standard address derived from a single key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressSet {
+    pub p2pkh_uncompressed: String,
+    pub p2pkh_compressed: String,
+    pub p2wpkh: String,
+    pub p2sh_p2wpkh: String,
+}
 
 #[derive(Debug, Clone)]
 pub struct Key {
     private: [u8; 32],
     pub public: Secp256k1Point,
+    network: Network,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct Signature {
     pub r: Vec<u8>,
     pub s: Vec<u8>,
 }
 
 impl Signature {
-    /// create a signature from BigUint
+    /// create a signature from BigUint, left padding `r` and `s` to 32 bytes
     pub fn from_biguint(r: BigUint, s: BigUint) -> Result<Self, String> {
-        if r.to_bytes_be().len() == 32 {
-            let r_vec = <[u8; 32]>::try_from(r.to_bytes_be()).unwrap().to_vec();
-            let s_vec = <[u8; 32]>::try_from(s.to_bytes_be()).unwrap().to_vec();
-            Ok(Signature::new(r_vec, s_vec).unwrap())
-        } else {
-            let r_vec = <[u8; 33]>::try_from(r.to_bytes_be()).unwrap().to_vec();
-            let s_vec = <[u8; 32]>::try_from(s.to_bytes_be()).unwrap().to_vec();
-            Ok(Signature::new(r_vec, s_vec).unwrap())
-        }
+        let r_bytes = to_32_bytes(&r.to_bytes_be()).map_err(|e| format!("Invalid r: {}", e))?;
+        let s_bytes = to_32_bytes(&s.to_bytes_be()).map_err(|e| format!("Invalid s: {}", e))?;
+        Signature::new(r_bytes.to_vec(), s_bytes.to_vec())
     }
 
     /// Create a Signature from two vectors.
     /// The `r` value can be 32 or 33 bytes; the `s`
     /// value should be 32 bytes
     pub fn new(r: Vec<u8>, s: Vec<u8>) -> Result<Self, String> {
-        println!("len == {}: {:?}", r.len(), r);
         if r.len() != 32 && r.len() != 33 {
             return Err("R value should have 32 or 33 bytes length".to_string());
         }
@@ -43,29 +95,81 @@ impl Signature {
         Ok(Self { r, s })
     }
 
+    /// Parse a strict DER encoded signature (BIP66): no trailing bytes,
+    /// no negative integers and no unnecessary zero padding
+    pub fn from_der(der: &[u8]) -> Result<Self, String> {
+        let (marker, rest) = der.split_first().ok_or("Empty DER signature")?;
+        if *marker != 0x30 {
+            return Err(format!("Invalid DER marker: {:#04x}", marker));
+        }
+        let (len, rest) = rest.split_first().ok_or("Missing DER length")?;
+        if *len as usize != rest.len() {
+            return Err(format!(
+                "DER length {} does not match the {} remaining bytes",
+                len,
+                rest.len()
+            ));
+        }
+
+        let (r, rest) = parse_der_integer(rest).map_err(|e| format!("Invalid r: {}", e))?;
+        let (s, rest) = parse_der_integer(rest).map_err(|e| format!("Invalid s: {}", e))?;
+        if !rest.is_empty() {
+            return Err(format!("{} trailing bytes after DER signature", rest.len()));
+        }
+
+        Signature::new(r.to_vec(), s.to_vec())
+    }
+
+    /// Serialize to the 64 bytes compact format `r || s`
+    pub fn to_compact(&self) -> Result<[u8; 64], String> {
+        let r = to_32_bytes(&self.r).map_err(|e| format!("Invalid r: {}", e))?;
+        let s = to_32_bytes(&self.s).map_err(|e| format!("Invalid s: {}", e))?;
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r);
+        compact[32..].copy_from_slice(&s);
+        Ok(compact)
+    }
+
+    /// Parse the 64 bytes compact format `r || s`
+    pub fn from_compact(compact: &[u8; 64]) -> Result<Self, String> {
+        Signature::new(compact[..32].to_vec(), compact[32..].to_vec())
+    }
+
     /// Serialize the current Signature struct to bitcoin's DER format
     pub fn der(&self) -> Result<Vec<u8>, String> {
         // start with 0x30 byte, equivalent 48u8
         let mut serialized = vec![48u8];
 
         let serialize = |element: &Vec<u8>| -> Result<Vec<u8>, String> {
-            if element.is_empty() {
-                return Err("Signature element cannot be empty.".to_string());
+            // DER integers are minimal, so drop any leading zero padding
+            let start = element.iter().position(|b| *b != 0);
+            let value = match start {
+                Some(i) => &element[i..],
+                None => return Err("Signature element cannot be zero.".to_string()),
+            };
+
+            // r and s are lower than the group order, so at most 32 bytes
+            if value.len() > 32 {
+                return Err(format!(
+                    "Signature element has {} significant bytes, expected at most 32.",
+                    value.len()
+                ));
             }
 
             // Append the 0x02 marker
             let mut res = vec![2u8];
 
-            // Prepend 0x00 if the first byte is >= 0x80 (MSB is set)
-            if element[0] >= 128u8 {
-                res.push((element.len() + 1) as u8);
+            // Prepend 0x00 if the first byte is >= 0x80 (MSB is set),
+            // otherwise the integer would be read as negative
+            if value[0] >= 0x80 {
+                res.push((value.len() + 1) as u8);
                 res.push(0u8);
             } else {
-                res.push(element.len() as u8);
+                res.push(value.len() as u8);
             }
 
             // Append the element itself
-            res.extend_from_slice(element.as_slice());
+            res.extend_from_slice(value);
             Ok(res)
         };
 
@@ -87,40 +191,116 @@ impl Signature {
         serialized.extend_from_slice(&s);
         Ok(serialized)
     }
+
+    /// Serialize the current Signature to DER format as a hexstring
+    pub fn to_hex_der(&self) -> Result<String, String> {
+        self.der().map(|der| to_hex(&der))
+    }
 }
 
+/// Implement Display trait to print `r` and `s` as hexadecimal strings
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Signature(r={}, s={})", to_hex(&self.r), to_hex(&self.s))
+    }
+}
+
+/// Implement Debug trait with the same hexadecimal representation
+/// instead of the default byte arrays
+impl fmt::Debug for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Signature")
+            .field("r", &to_hex(&self.r))
+            .field("s", &to_hex(&self.s))
+            .finish()
+    }
+}
+
+/// Compare the private keys without short-circuiting on the first
+/// differing byte, so the comparison time does not leak the key, then
+/// the networks, since the same secret has another WIF and address
+/// on each of them
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        let same_private = self
+            .private
+            .iter()
+            .zip(other.private.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+        same_private && self.network == other.network
+    }
+}
+
+impl Eq for Key {}
+
 /// Implements a struct representation that stores
 /// a private key and its correspondent public key
 impl Key {
-    /// Create a Secp256k1Point from a given private key represented as bytes
+    /// Create a Secp256k1Point from a given private key represented as bytes.
+    /// Fails if the key isnt in the range [1, order)
     pub fn to_public(private: &[u8; 32]) -> Result<Secp256k1Point, String> {
-        let prime = Secp256k1::Prime.as_biguint().to_str_radix(16);
-        let p = prime.as_str();
-        let private_num = BigUint::from_bytes_be(private).to_str_radix(16);
-        let private_fe = FieldElement::new(private_num.as_str(), p).unwrap();
-        let g = Secp256k1::Generator.as_point();
-        Ok(private_fe.num * g)
+        let private_num = BigUint::from_bytes_be(private);
+        if private_num.is_zero() || private_num >= Secp256k1::Order.as_biguint() {
+            return Err("Private key isnt in the range [1..order)".to_string());
+        }
+        Ok(Secp256k1Point::generator().mul_ct(&private_num))
     }
 
-    /// Create a Key from a private key represented as 32 bytes
+    /// Create a Key from a private key represented as 32 bytes,
+    /// bound to mainnet
     pub fn from_bytes_be(private: [u8; 32]) -> Result<Self, String> {
-        let public = Self::to_public(&private).unwrap();
-        Ok(Self { private, public })
+        let public = Self::to_public(&private)?;
+        Ok(Self {
+            private,
+            public,
+            network: Network::Mainnet,
+        })
+    }
+
+    /// Bind the key to another network
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// The network the key is bound to
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Return the compressed P2PKH address of the key on its network
+    pub fn address(&self) -> Result<String, String> {
+        self.to_pubkey_hash(true, self.network.is_testnet())
+    }
+
+    /// Encode the private key in Wallet Import Format for its network,
+    /// flagged to use the compressed public key
+    pub fn to_wif(&self) -> Result<String, String> {
+        let mut payload = self.private.to_vec();
+        payload.push(0x01);
+        encode_base58check_versioned(self.network.wif_prefix(), &payload)
+            .map_err(|e| format!("Failed to encode WIF: {:?}", e))
+    }
+
+    /// Create a Key from a private key represented as an integer
+    /// in the range [1, order)
+    pub fn from_biguint(n: BigUint) -> Result<Self, String> {
+        let order = Secp256k1::Order.as_biguint();
+        if n.is_zero() || n >= order {
+            return Err(format!("Private key {} isnt in the range [1..order)", n));
+        }
+
+        // Left pad the big-endian bytes with zeros
+        let bytes = n.to_bytes_be();
+        let mut private = [0u8; 32];
+        private[(32 - bytes.len())..].copy_from_slice(&bytes);
+        Self::from_bytes_be(private)
     }
 
     /// Create a Key from a private key represented as 32 bytes hexstring
     pub fn from_hexstr(private: &str) -> Result<Self, String> {
-        // Decode the hexadecimal string into a Vec<u8>
-        let decoded = match hex::decode(private) {
-            Ok(bytes) => bytes,
-            Err(_) => return Err("Invalid hexadecimal string".to_string()),
-        };
-
-        // Ensure the decoded vector is exactly 32 bytes long
-        let bytes_private: [u8; 32] = match <[u8; 32]>::try_from(decoded.as_slice()) {
-            Ok(arr) => arr,
-            Err(_) => return Err("Hexadecimal string does not decode to 32 bytes".to_string()),
-        };
+        let bytes_private = from_hex_32(private)?;
         Self::from_bytes_be(bytes_private)
     }
 
@@ -128,121 +308,357 @@ impl Key {
     /// Deterministic Usage of the Digital Signature Algorithm (DSA)
     /// and Elliptic Curve Digital Signature Algorithm (ECDSA)
     pub fn deterministic_k(&self, z: &[u8; 32]) -> Result<[u8; 32], String> {
+        self.rfc6979_candidates(z, |k| {
+            let bytes = k.to_bytes_be();
+            let mut result = [0u8; 32];
+            result[(32 - bytes.len())..].copy_from_slice(&bytes);
+            Some(result)
+        })
+    }
+
+    /// Walk the RFC6979 candidates in [1, order) until `accept` returns
+    /// a value, regenerating `k` and `v` as in step h.3 on rejection.
+    /// `sign` uses it to skip the nonces giving a zero `r` or `s`
+    pub fn rfc6979_candidates<T>(
+        &self,
+        z: &[u8; 32],
+        mut accept: impl FnMut(&BigUint) -> Option<T>,
+    ) -> Result<T, String> {
         // Define constants
         let ord = Secp256k1::Order.as_biguint();
 
+        // RFC6979 bits2octets: z is reduced modulo the order
+        let z = to_32_bytes(&(BigUint::from_bytes_be(z) % &ord).to_bytes_be())?;
+
         // Define byte variables
         let mut k_bytes = vec![0u8; 32];
         let mut v_bytes = vec![1u8; 32];
 
-        // Closure to update HMAC
-
         // Redefine k with byte 00
-        k_bytes = hmac256(&k_bytes, &[&v_bytes, &[0u8], &self.private, z])?;
-        v_bytes = hmac256(&k_bytes, &[&v_bytes])?;
-        k_bytes = hmac256(&k_bytes, &[&v_bytes, &[1u8], &self.private, z])?;
-        v_bytes = hmac256(&k_bytes, &[&v_bytes])?;
+        k_bytes = hmac256(&k_bytes, &[&v_bytes, &[0u8], &self.private, &z])?;
+        v_bytes = hmac256_one(&k_bytes, &v_bytes)?;
+        k_bytes = hmac256(&k_bytes, &[&v_bytes, &[1u8], &self.private, &z])?;
+        v_bytes = hmac256_one(&k_bytes, &v_bytes)?;
 
         loop {
-            v_bytes = hmac256(&k_bytes, &[&v_bytes])?;
+            v_bytes = hmac256_one(&k_bytes, &v_bytes)?;
             let k = BigUint::from_bytes_be(&v_bytes);
             if k >= BigUint::one() && k < ord {
-                let result = <[u8; 32]>::try_from(k.to_bytes_be()).unwrap();
-                return Ok(result);
+                if let Some(result) = accept(&k) {
+                    return Ok(result);
+                }
             }
             k_bytes = hmac256(&k_bytes, &[&v_bytes, &[0u8]])?;
-            v_bytes = hmac256(&k_bytes, &[&v_bytes])?;
+            v_bytes = hmac256_one(&k_bytes, &v_bytes)?;
         }
     }
 
     /// Sign a BIP 62 compliant hashed message
+    ///
+    /// The nonce is the first RFC6979 candidate that yields non-zero
+    /// `r` and `s`; the following candidates are tried otherwise
     pub fn sign(&self, z: [u8; 32]) -> Result<Signature, String> {
+        self.rfc6979_candidates(&z, |k| self.sign_with_nonce(z, k).ok())
+    }
+
+    /// Sign a BIP 62 compliant hashed message with the given nonce `k`.
+    /// Fails if `k` is not in [1, order) or if `r` or `s` is zero, in
+    /// which case another nonce must be used
+    pub fn sign_with_nonce(&self, z: [u8; 32], k: &BigUint) -> Result<Signature, String> {
         // Extract some required constants
-        let g = Secp256k1::Generator.as_point();
+        let g = Secp256k1Point::generator();
         let two = BigUint::from(2u32);
         let ord = Secp256k1::Order.as_biguint();
 
-        // convert z to num
-        let z_num = BigUint::from_bytes_be(&z);
-        let e_num = BigUint::from_bytes_be(&self.private);
+        if k.is_zero() || k >= &ord {
+            return Err("Nonce isnt in the range [1..order)".to_string());
+        }
 
-        // Generate deterministic k
-        let k = self.deterministic_k(&z)?;
-        let k_num = BigUint::from_bytes_be(&k);
+        // convert z to num, reduced modulo the order
+        let z_num = BigUint::from_bytes_be(&z) % &ord;
+        let e_num = BigUint::from_bytes_be(&self.private);
 
         // Calculate r = (k * G).x
-        let r_point = &k_num * &g;
-        let r_num = r_point.x.unwrap().num % &ord;
+        let r_point = g.mul_ct(k);
+        let r_num = &r_point.x().unwrap().num % &ord;
+        if r_num.is_zero() {
+            return Err("Nonce produces a zero r".to_string());
+        }
 
         // Calculate k_inv = k^(ord-2) mod ord
-        let k_inv = &k_num.modpow(&(&ord - &two), &ord);
+        let k_inv = &mod_inverse_order(k);
 
         // Calculate s = k_inv * (z + r * private_key) mod ord
         let mut s_num = (k_inv * (&z_num + (&r_num * &e_num) % &ord)) % &ord;
+        if s_num.is_zero() {
+            return Err("Nonce produces a zero s".to_string());
+        }
 
         // Ensure low-S compliance
         if s_num > (&ord / &two) {
             s_num = &ord - &s_num;
         }
 
-        Ok(Signature::from_biguint(r_num, s_num).unwrap())
+        Signature::from_biguint(r_num, s_num)
     }
 
     /// Apply signature verification from a given hashed message
     pub fn verify(&self, z: &[u8; 32], signature: &Signature) -> bool {
         // define some "constants"
-        let two = BigUint::from(2u32);
         let ord = Secp256k1::Order.as_biguint();
-        let generator = Secp256k1::Generator.as_point();
+        let generator = Secp256k1Point::generator();
 
         let z_num = BigUint::from_bytes_be(z);
         let s_num = BigUint::from_bytes_be(signature.s.as_slice());
         let r_num = BigUint::from_bytes_be(signature.r.as_slice());
 
-        let exp = &ord - &two;
-        let s_inv = s_num.modpow(&exp, &ord);
+        // r and s must be in [1, order), e.g. when parsed from untrusted DER
+        if r_num.is_zero() || s_num.is_zero() || r_num >= ord || s_num >= ord {
+            return false;
+        }
+
+        let s_inv = mod_inverse_order(&s_num);
 
         let u = (&z_num * &s_inv) % &ord;
-        let v = (&r_num * &s_inv) % ord;
+        let v = (&r_num * &s_inv) % &ord;
+
+        let total = Secp256k1Point::mul_add(&u, generator, &v, &self.public);
 
-        let u_g = u * generator;
-        let v_p = v * &self.public;
-        let total = u_g + v_p;
+        match total.x() {
+            Some(x) => &x.num % &ord == r_num,
+            None => false,
+        }
+    }
 
-        total.x.unwrap().num == r_num
+    /// Verify a DER encoded signature of a hashed message. A malformed
+    /// DER is an `Err`, while a well formed but wrong signature is `Ok(false)`
+    pub fn verify_der(&self, z: &[u8; 32], der: &[u8]) -> Result<bool, String> {
+        let signature = Signature::from_der(der)?;
+        Ok(self.verify(z, &signature))
     }
 
     /// Return an address string (P2PKH format)
     pub fn to_pubkey_hash(&self, compressed: bool, testnet: bool) -> Result<String, String> {
-        // Get the public key
-        let pubkey = &self.public;
-
-        // Generate the SEC (serialized public key) and hash160
-        let h160 = if compressed {
-            pubkey
-                .to_compressed_sec()
-                .map_err(|e| format!("Failed to compress public key: {:?}", e))
-                .and_then(|sec| {
-                    hash160(&sec).map_err(|e| format!("Failed to hash public key: {:?}", e))
-                })?
-        } else {
-            pubkey
-                .to_uncompressed_sec()
-                .map_err(|e| format!("Failed to uncompress public key: {:?}", e))
-                .and_then(|sec| {
-                    hash160(&sec).map_err(|e| format!("Failed to hash public key: {:?}", e))
-                })?
-        };
+        self.public.to_p2pkh_address(compressed, testnet)
+    }
+
+    /// Return the hash160 of the compressed SEC public key,
+    /// used as the witness program of P2WPKH outputs
+    fn compressed_hash160(&self) -> Result<[u8; 20], String> {
+        self.public.serialize(true).and_then(|sec| {
+            hash160(&sec).map_err(|e| format!("Failed to hash public key: {:?}", e))
+        })
+    }
+
+    /// Return the first four bytes of the compressed public key hash160,
+    /// used by BIP32 as the parent fingerprint of extended keys
+    pub fn fingerprint(&self) -> Result<[u8; 4], String> {
+        let h160 = self.compressed_hash160()?;
+        Ok([h160[0], h160[1], h160[2], h160[3]])
+    }
+
+    /// Return a native segwit address (P2WPKH format, BIP173)
+    pub fn p2wpkh_address(&self, testnet: bool) -> Result<String, String> {
+        let h160 = self.compressed_hash160()?;
+        let hrp = if testnet { TESTNET_HRP } else { MAINNET_HRP };
+        encode_segwit_address(hrp, 0, &h160)
+            .map_err(|e| format!("Failed to encode address: {:?}", e))
+    }
+
+    /// Return a segwit address nested in P2SH (P2SH-P2WPKH format, BIP141)
+    pub fn p2sh_p2wpkh_address(&self, testnet: bool) -> Result<String, String> {
+        let h160 = self.compressed_hash160()?;
+
+        // The redeem script is the witness program: OP_0 <20 bytes hash>
+        let mut redeem_script = vec![0u8, 20u8];
+        redeem_script.extend_from_slice(&h160);
+        let script_hash = hash160(&redeem_script)
+            .map_err(|e| format!("Failed to hash redeem script: {:?}", e))?;
 
-        // Determine the prefix and construct the address
         let prefix = if testnet {
-            TESTNET_PREFIX
+            TESTNET_P2SH_PREFIX
         } else {
-            MAINNET_PREFIX
+            MAINNET_P2SH_PREFIX
         };
-        let mut result = vec![prefix];
-        result.extend_from_slice(&h160);
+        encode_base58check_versioned(prefix, &script_hash)
+            .map_err(|e| format!("Failed to encode address: {:?}", e))
+    }
 
-        encode_base58check(&result).map_err(|e| format!("Failed to encode address: {:?}", e))
+    /// Return the uncompressed and compressed P2PKH, the P2WPKH
+    /// and the P2SH-P2WPKH addresses of the key
+    pub fn all_addresses(&self, testnet: bool) -> Result<AddressSet, String> {
+        Ok(AddressSet {
+            p2pkh_uncompressed: self.to_pubkey_hash(false, testnet)?,
+            p2pkh_compressed: self.to_pubkey_hash(true, testnet)?,
+            p2wpkh: self.p2wpkh_address(testnet)?,
+            p2sh_p2wpkh: self.p2sh_p2wpkh_address(testnet)?,
+        })
+    }
+}
+
+/// Verify a BIP340 Schnorr signature `r || s` of a 32 bytes message
+/// against an x-only public key
+pub fn verify_schnorr(pubkey_x: &[u8; 32], msg: &[u8; 32], sig: &[u8; 64]) -> bool {
+    let public = match Secp256k1Point::lift_x(pubkey_x) {
+        Ok(point) => point,
+        Err(_) => return false,
+    };
+
+    let ord = Secp256k1::Order.as_biguint();
+    let r = BigUint::from_bytes_be(&sig[..32]);
+    let s = BigUint::from_bytes_be(&sig[32..]);
+    if r >= Secp256k1::Prime.as_biguint() || s >= ord {
+        return false;
+    }
+
+    let e = match tagged_hash("BIP0340/challenge", &[&sig[..32], pubkey_x, msg]) {
+        Ok(hash) => BigUint::from_bytes_be(&hash) % &ord,
+        Err(_) => return false,
+    };
+
+    // R = s * G - e * P
+    let total = Secp256k1Point::mul_add(&s, Secp256k1Point::generator(), &(&ord - e), &public);
+    match (total.x(), total.y_is_even()) {
+        (Some(x), Some(true)) => x.num == r,
+        _ => false,
+    }
+}
+
+/// Verify the Schnorr signature of a taproot key path spend (BIP341)
+/// against the 32 bytes output key of the spent output
+pub fn verify_taproot_keypath(output_key_x: &[u8; 32], sighash: &[u8; 32], sig: &[u8; 64]) -> bool {
+    verify_schnorr(output_key_x, sighash, sig)
+}
+
+/// Validate a base58 address (P2PKH or P2SH), checking its checksum,
+/// length and version byte
+pub fn validate_address(addr: &str) -> Result<AddressInfo, String> {
+    let data = decode_base58(addr)?;
+
+    // version byte + hash160
+    if data.len() != 21 {
+        return Err(format!(
+            "Invalid address length: expected 21 bytes, got {}",
+            data.len()
+        ));
+    }
+
+    let (network, address_type) = match data[0] {
+        MAINNET_PREFIX => (Network::Mainnet, AddressType::P2pkh),
+        TESTNET_PREFIX => (Network::Testnet, AddressType::P2pkh),
+        MAINNET_P2SH_PREFIX => (Network::Mainnet, AddressType::P2sh),
+        TESTNET_P2SH_PREFIX => (Network::Testnet, AddressType::P2sh),
+        version => return Err(format!("Unknown address version byte {:#04x}", version)),
+    };
+
+    let hash = <[u8; 20]>::try_from(&data[1..]).unwrap();
+    Ok(AddressInfo {
+        network,
+        address_type,
+        hash,
+    })
+}
+
+/// Longest prefix accepted by `find_vanity_address`, including the
+/// network character. Each extra character multiplies the search by
+/// about 58, and every try costs a key generation and an address
+#[cfg(feature = "rand")]
+const MAX_VANITY_PREFIX_LEN: usize = 2;
+
+/// Maximum number of keys tried by `find_vanity_address`. A common
+/// second character takes a few dozen tries, the rarest ones (like
+/// `1q`) about two thousand
+#[cfg(feature = "rand")]
+const MAX_VANITY_ITERATIONS: usize = 5_000;
+
+/// Generate random keys until its compressed P2PKH address
+/// starts with the given prefix
+#[cfg(feature = "rand")]
+pub fn find_vanity_address(prefix: &str, testnet: bool) -> Result<Key, String> {
+    if let Some(c) = prefix.chars().find(|c| !ALPHABET.contains(*c)) {
+        return Err(format!("Invalid character '{}' in Base58 prefix", c));
+    }
+
+    if prefix.len() > MAX_VANITY_PREFIX_LEN {
+        return Err(format!(
+            "Prefix '{}' is too long, the maximum is {} characters",
+            prefix, MAX_VANITY_PREFIX_LEN
+        ));
+    }
+
+    // P2PKH addresses start with '1' on mainnet and 'm' or 'n' on testnet
+    let valid_start = if testnet { "mn" } else { "1" };
+    if let Some(c) = prefix.chars().next() {
+        if !valid_start.contains(c) {
+            return Err(format!(
+                "Addresses on this network cannot start with '{}'",
+                c
+            ));
+        }
+    }
+
+    let network = if testnet {
+        Network::Testnet
+    } else {
+        Network::Mainnet
+    };
+
+    for _ in 0..MAX_VANITY_ITERATIONS {
+        let n = BigUint::from_bytes_be(&rand::random::<[u8; 32]>());
+        let key = match Key::from_biguint(n) {
+            Ok(key) => key.with_network(network),
+            Err(_) => continue,
+        };
+
+        if key.address()?.starts_with(prefix) {
+            return Ok(key);
+        }
+    }
+
+    Err(format!(
+        "No address with prefix '{}' found after {} iterations",
+        prefix, MAX_VANITY_ITERATIONS
+    ))
+}
+
+/// Strip the leading zeros of a big-endian integer and left pad it to
+/// 32 bytes, failing if it has more than 32 significant bytes
+fn to_32_bytes(bytes: &[u8]) -> Result<[u8; 32], String> {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    let value = &bytes[start..];
+    if value.len() > 32 {
+        return Err(format!(
+            "{} significant bytes, expected at most 32",
+            value.len()
+        ));
+    }
+    let mut result = [0u8; 32];
+    result[(32 - value.len())..].copy_from_slice(value);
+    Ok(result)
+}
+
+/// Parse one `0x02 <len> <value>` DER integer, returning it left padded
+/// to 32 bytes along with the remaining bytes
+fn parse_der_integer(bytes: &[u8]) -> Result<([u8; 32], &[u8]), String> {
+    let (marker, rest) = bytes.split_first().ok_or("Missing integer marker")?;
+    if *marker != 0x02 {
+        return Err(format!("Invalid integer marker: {:#04x}", marker));
+    }
+    let (len, rest) = rest.split_first().ok_or("Missing integer length")?;
+    let len = *len as usize;
+    if len == 0 || len > rest.len() {
+        return Err(format!("Invalid integer length: {}", len));
+    }
+    let (value, rest) = rest.split_at(len);
+
+    if value[0] & 0x80 != 0 {
+        return Err("Negative integer".to_string());
+    }
+    if value.iter().all(|b| *b == 0) {
+        return Err("Zero integer".to_string());
+    }
+    if len > 1 && value[0] == 0 && value[1] & 0x80 == 0 {
+        return Err("Unnecessary zero padding".to_string());
     }
+    Ok((to_32_bytes(value)?, rest))
 }
@@ -1,7 +1,12 @@
 use field_element::FieldElement;
 use hasher::{double_sha256, sha256};
-use key::{Key, Signature};
-use secp256k1::{Secp256k1Point, PRIME};
+#[cfg(feature = "rand")]
+use key::find_vanity_address;
+use key::{
+    validate_address, verify_schnorr, verify_taproot_keypath, AddressSet, AddressType, Key,
+    Network, Signature,
+};
+use secp256k1::{Secp256k1, Secp256k1Point, PRIME};
 
 #[cfg(test)]
 mod tests {
@@ -50,6 +55,21 @@ mod tests {
         assert!(Key::from_hexstr(prv).is_ok());
     }
 
+    #[test]
+    fn test_from_bytes_be_fail_out_of_range() {
+        // zero would make the public key the point at infinity
+        assert!(Key::to_public(&[0u8; 32]).is_err());
+        assert!(Key::from_bytes_be([0u8; 32]).is_err());
+
+        // the order itself and anything above it
+        let order = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141";
+        assert!(Key::from_hexstr(order).is_err());
+        assert!(Key::from_bytes_be([0xffu8; 32]).is_err());
+
+        let order_minus_one = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364140";
+        assert!(Key::from_hexstr(order_minus_one).is_ok());
+    }
+
     #[test]
     fn test_sha256() {
         let expected_sha = [
@@ -260,4 +280,604 @@ mod tests {
         let der = signature.der().unwrap();
         assert_eq!(der, expected_der);
     }
+
+    #[test]
+    fn test_signature_display() {
+        let r = BigUint::from_str_radix(
+            "37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6",
+            16,
+        )
+        .unwrap();
+
+        let s = BigUint::from_str_radix(
+            "8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+            16,
+        )
+        .unwrap();
+
+        let signature = Signature::from_biguint(r, s).unwrap();
+        assert_eq!(
+            format!("{}", signature),
+            "Signature(r=37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6, s=8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec)"
+        );
+        assert_eq!(
+            format!("{:?}", signature),
+            "Signature { r: \"37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6\", s: \"8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec\" }"
+        );
+    }
+
+    #[test]
+    fn test_to_hex_der() {
+        let r = BigUint::from_str_radix(
+            "37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6",
+            16,
+        )
+        .unwrap();
+
+        let s = BigUint::from_str_radix(
+            "8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+            16,
+        )
+        .unwrap();
+
+        let signature = Signature::from_biguint(r, s).unwrap();
+        assert_eq!(
+            signature.to_hex_der().unwrap(),
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c60221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"
+        );
+    }
+
+    #[test]
+    fn test_p2wpkh_address() {
+        let prv = "0000000000000000000000000000000000000000000000000000000000000001";
+        let key = Key::from_hexstr(prv).unwrap();
+        assert_eq!(
+            key.p2wpkh_address(false).unwrap(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            key.p2wpkh_address(true).unwrap(),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+    }
+
+    #[test]
+    fn test_p2sh_p2wpkh_address() {
+        let prv = "0000000000000000000000000000000000000000000000000000000000000001";
+        let key = Key::from_hexstr(prv).unwrap();
+        assert_eq!(
+            key.p2sh_p2wpkh_address(false).unwrap(),
+            "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN"
+        );
+        assert_eq!(
+            key.p2sh_p2wpkh_address(true).unwrap(),
+            "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN"
+        );
+    }
+
+    #[test]
+    fn test_to_pubkey_hash() {
+        let prv = "0000000000000000000000000000000000000000000000000000000000000001";
+        let key = Key::from_hexstr(prv).unwrap();
+        assert_eq!(
+            key.to_pubkey_hash(true, false).unwrap(),
+            "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+        );
+        assert_eq!(
+            key.to_pubkey_hash(true, true).unwrap(),
+            "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+        );
+    }
+
+    #[test]
+    fn test_from_biguint() {
+        // 0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1
+        let expected_sec = [
+            3u8, 87u8, 164u8, 243u8, 104u8, 134u8, 138u8, 138u8, 109u8, 87u8, 41u8, 145u8, 228u8,
+            132u8, 230u8, 100u8, 129u8, 15u8, 241u8, 76u8, 5u8, 192u8, 250u8, 2u8, 50u8, 117u8,
+            37u8, 17u8, 81u8, 254u8, 14u8, 83u8, 209u8,
+        ];
+
+        let key = Key::from_biguint(BigUint::from_u32(5001u32).unwrap()).unwrap();
+        let sec = key.public.to_compressed_sec().unwrap();
+        assert_eq!(sec, expected_sec);
+    }
+
+    #[test]
+    fn test_from_biguint_fail_out_of_range() {
+        let order = BigUint::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            16,
+        )
+        .unwrap();
+        assert!(Key::from_biguint(BigUint::from_u32(0u32).unwrap()).is_err());
+        assert!(Key::from_biguint(order).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_find_vanity_address() {
+        // About one mainnet address in 22 starts with "1A"
+        let key = find_vanity_address("1A", false).unwrap();
+        assert!(key.to_pubkey_hash(true, false).unwrap().starts_with("1A"));
+        assert!(key.address().unwrap().starts_with("1A"));
+
+        // Testnet addresses start with 'm' or 'n', about evenly
+        let key = find_vanity_address("n", true).unwrap();
+        assert!(key.to_pubkey_hash(true, true).unwrap().starts_with('n'));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_find_vanity_address_fail() {
+        // '0' isnt a base58 character
+        assert!(find_vanity_address("10", false).is_err());
+        // Too long to be found quickly
+        assert!(find_vanity_address("1ab", false).is_err());
+        // Mainnet addresses always start with '1'
+        assert!(find_vanity_address("2", false).is_err());
+    }
+
+    #[test]
+    fn test_validate_address_mainnet_p2pkh() {
+        let info = validate_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH").unwrap();
+        assert_eq!(info.network, Network::Mainnet);
+        assert_eq!(info.address_type, AddressType::P2pkh);
+        assert_eq!(
+            info.hash,
+            [
+                117u8, 30u8, 118u8, 232u8, 25u8, 145u8, 150u8, 212u8, 84u8, 148u8, 28u8, 69u8,
+                209u8, 179u8, 163u8, 35u8, 241u8, 67u8, 59u8, 214u8,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_address_testnet_p2sh() {
+        let info = validate_address("2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN").unwrap();
+        assert_eq!(info.network, Network::Testnet);
+        assert_eq!(info.address_type, AddressType::P2sh);
+    }
+
+    #[test]
+    fn test_validate_address_fail_checksum() {
+        assert!(validate_address("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ").is_err());
+    }
+
+    #[test]
+    fn test_validate_address_fail_unknown_version() {
+        // A base58check encoded private key (WIF) isnt an address
+        assert!(validate_address("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").is_err());
+    }
+
+    #[test]
+    fn test_key_eq() {
+        let a = Key::from_biguint(BigUint::from(12345u32)).unwrap();
+        let b = Key::from_biguint(BigUint::from(12345u32)).unwrap();
+        let c = Key::from_biguint(BigUint::from(12346u32)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        // same secret on another network
+        let testnet = b.clone().with_network(Network::Testnet);
+        assert_ne!(a, testnet);
+        assert_eq!(a.with_network(Network::Testnet), testnet);
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // hash160 of the compressed SEC of 1*G is 751e76e8199196d454941c45d1b3a323f1433bd6
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        assert_eq!(key.fingerprint().unwrap(), [0x75, 0x1e, 0x76, 0xe8]);
+    }
+
+    #[test]
+    fn test_fingerprint_fail_infinity() {
+        let mut key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        key.public = Secp256k1Point::infinity();
+        assert!(key.fingerprint().is_err());
+    }
+
+    #[test]
+    fn test_der_r_high_bit_set() {
+        // r starts exactly with 0x80, so it needs a 0x00 pad byte
+        let mut r = vec![0x11u8; 32];
+        r[0] = 0x80;
+        let s = vec![0x22u8; 32];
+        let der = Signature::new(r.clone(), s.clone()).unwrap().der().unwrap();
+
+        let mut expected = vec![0x30, 0x45, 0x02, 0x21, 0x00];
+        expected.extend_from_slice(&r);
+        expected.extend_from_slice(&[0x02, 0x20]);
+        expected.extend_from_slice(&s);
+        assert_eq!(der, expected);
+
+        // an already padded 33 bytes r serializes the same way
+        let mut padded = vec![0u8];
+        padded.extend_from_slice(&r);
+        let der_padded = Signature::new(padded, s).unwrap().der().unwrap();
+        assert_eq!(der_padded, expected);
+    }
+
+    #[test]
+    fn test_der_r_high_bit_unset() {
+        // r starts with 0x7f and a short r is not zero-padded in DER
+        let mut r = vec![0x11u8; 32];
+        r[0] = 0x7f;
+        let s = vec![0x22u8; 32];
+        let der = Signature::new(r.clone(), s.clone()).unwrap().der().unwrap();
+        assert_eq!(&der[..4], &[0x30, 0x44, 0x02, 0x20]);
+        assert_eq!(&der[4..36], r.as_slice());
+
+        let mut short_r = vec![0u8; 32];
+        short_r[31] = 0x01;
+        let der = Signature::new(short_r, s).unwrap().der().unwrap();
+        assert_eq!(&der[..5], &[0x30, 0x25, 0x02, 0x01, 0x01]);
+    }
+
+    #[test]
+    fn test_der_fail_invalid_elements() {
+        let s = vec![0x22u8; 32];
+        // 33 significant bytes cannot be lower than the group order
+        assert!(Signature::new(vec![0x01u8; 33], s.clone())
+            .unwrap()
+            .der()
+            .is_err());
+        // zero is not a valid r
+        assert!(Signature::new(vec![0u8; 32], s).unwrap().der().is_err());
+        // an empty s
+        assert!(Signature::new(vec![0x01u8; 32], vec![])
+            .unwrap()
+            .der()
+            .is_err());
+    }
+
+    #[test]
+    fn test_all_addresses() {
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        assert_eq!(
+            key.all_addresses(false).unwrap(),
+            AddressSet {
+                p2pkh_uncompressed: "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm".to_string(),
+                p2pkh_compressed: "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".to_string(),
+                p2wpkh: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+                p2sh_p2wpkh: "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN".to_string(),
+            }
+        );
+        assert_eq!(
+            key.all_addresses(true).unwrap(),
+            AddressSet {
+                p2pkh_uncompressed: "mtoKs9V381UAhUia3d7Vb9GNak8Qvmcsme".to_string(),
+                p2pkh_compressed: "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r".to_string(),
+                p2wpkh: "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string(),
+                p2sh_p2wpkh: "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_sign_with_nonce_matches_sign() {
+        let key = Key::from_biguint(BigUint::from(12345u32)).unwrap();
+        let z = sha256(b"Hello, world").unwrap();
+        let k = BigUint::from_bytes_be(&key.deterministic_k(&z).unwrap());
+        let signature = key.sign_with_nonce(z, &k).unwrap();
+        assert_eq!(signature.der(), key.sign(z).unwrap().der());
+        assert!(key.verify(&z, &signature));
+    }
+
+    #[test]
+    fn test_sign_with_nonce_rejects_zero_s() {
+        // r == 0 would need the discrete log of a point with x == 0 mod order,
+        // so force the other rejected case: with e == 1, s == 0 when z == -r
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        let order = Secp256k1::Order.as_biguint();
+        let k = BigUint::from(2u32);
+        let r = &Secp256k1::Generator.as_point().mul_u64(2).x().unwrap().num % &order;
+        let z_num = (&order - r) % &order;
+
+        let mut z = [0u8; 32];
+        let bytes = z_num.to_bytes_be();
+        z[(32 - bytes.len())..].copy_from_slice(&bytes);
+
+        assert!(key.sign_with_nonce(z, &k).is_err());
+
+        // sign moves on to a nonce producing a valid signature
+        let signature = key.sign(z).unwrap();
+        assert!(key.verify(&z, &signature));
+    }
+
+    #[test]
+    fn test_sign_with_nonce_fail_out_of_range() {
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        let z = sha256(b"Hello, world").unwrap();
+        assert!(key.sign_with_nonce(z, &BigUint::from(0u32)).is_err());
+        assert!(key
+            .sign_with_nonce(z, &Secp256k1::Order.as_biguint())
+            .is_err());
+    }
+
+    /// Deterministic pseudo random scalars in [1, order), derived
+    /// by hashing a fixed seed with a counter
+    fn seeded_scalars(seed: &[u8], count: usize) -> Vec<BigUint> {
+        let order = Secp256k1::Order.as_biguint();
+        let mut scalars = Vec::with_capacity(count);
+        let mut counter = 0u32;
+        while scalars.len() < count {
+            let mut preimage = seed.to_vec();
+            preimage.extend_from_slice(&counter.to_be_bytes());
+            counter += 1;
+
+            let digest = sha256(&preimage).unwrap();
+            // Shift some values right so short and leading-zero
+            // encodings are exercised too
+            let n = (BigUint::from_bytes_be(&digest) >> (counter % 17 * 8)) % &order;
+            if n != BigUint::from(0u32) {
+                scalars.push(n);
+            }
+        }
+        scalars
+    }
+
+    fn assert_signature_round_trip(r: BigUint, s: BigUint) {
+        let signature = Signature::from_biguint(r, s).unwrap();
+
+        let der = signature.der().unwrap();
+        assert_eq!(Signature::from_der(&der).unwrap(), signature);
+
+        let compact = signature.to_compact().unwrap();
+        assert_eq!(Signature::from_compact(&compact).unwrap(), signature);
+    }
+
+    #[test]
+    fn test_signature_round_trip_fuzz() {
+        let scalars = seeded_scalars(b"signature round trip", 400);
+        for pair in scalars.chunks(2) {
+            assert_signature_round_trip(pair[0].clone(), pair[1].clone());
+        }
+    }
+
+    #[test]
+    fn test_signature_round_trip_edge_cases() {
+        let order = Secp256k1::Order.as_biguint();
+        let one = BigUint::from(1u32);
+        let high_bit = BigUint::from(0x80u32) << 248u32;
+        let padded_high_bit = BigUint::from(0x80u32) << 240u32;
+        let values = [
+            one.clone(),
+            BigUint::from(0x7fu32),
+            BigUint::from(0x80u32),
+            high_bit.clone(),
+            padded_high_bit,
+            &order - &one,
+        ];
+        for r in values.iter() {
+            for s in values.iter() {
+                assert_signature_round_trip(r.clone(), s.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_der_fail_non_strict() {
+        let signature = Signature::from_biguint(BigUint::from(1u32), BigUint::from(2u32)).unwrap();
+        let der = signature.der().unwrap();
+        assert_eq!(der, vec![0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]);
+
+        // empty, wrong marker, wrong length and trailing bytes
+        assert!(Signature::from_der(&[]).is_err());
+        assert!(Signature::from_der(&[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]).is_err());
+        assert!(Signature::from_der(&[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]).is_err());
+        assert!(
+            Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x00]).is_err()
+        );
+        // negative r, unnecessary padding, zero s and truncated s
+        assert!(Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x81, 0x02, 0x01, 0x02]).is_err());
+        assert!(
+            Signature::from_der(&[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x02]).is_err()
+        );
+        assert!(Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x00]).is_err());
+        assert!(Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x02, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_from_biguint_fail_too_large() {
+        let too_large = BigUint::from(1u32) << 256u32;
+        assert!(Signature::from_biguint(too_large.clone(), BigUint::from(1u32)).is_err());
+        assert!(Signature::from_biguint(BigUint::from(1u32), too_large).is_err());
+    }
+
+    #[test]
+    fn test_deterministic_k_reduces_z() {
+        // z = order + 0x1234, the nonce matches a reference RFC6979
+        // implementation and the one for z = 0x1234
+        let z = [
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xfe, 0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c,
+            0xd0, 0x36, 0x53, 0x75,
+        ];
+        let mut reduced = [0u8; 32];
+        reduced[30] = 0x12;
+        reduced[31] = 0x34;
+        let expected = BigUint::from_str_radix(
+            "c3e33d1c31b32f9699791e578518f3b3c8a57a666ed60ddc0e40b52c439e7e5c",
+            16,
+        )
+        .unwrap();
+
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        let k = key.deterministic_k(&z).unwrap();
+        assert_eq!(BigUint::from_bytes_be(&k), expected);
+        assert_eq!(key.deterministic_k(&reduced).unwrap(), k);
+
+        let signature = key.sign(z).unwrap();
+        assert_eq!(signature, key.sign(reduced).unwrap());
+        assert!(key.verify(&z, &signature));
+    }
+
+    #[test]
+    fn test_verify_der() {
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        let z = sha256(b"Hello, world").unwrap();
+        let der = key.sign(z).unwrap().der().unwrap();
+        assert_eq!(key.verify_der(&z, &der), Ok(true));
+
+        // a well formed signature for another message does not verify
+        let other = sha256(b"Goodbye, world").unwrap();
+        assert_eq!(key.verify_der(&other, &der), Ok(false));
+
+        // a malformed DER is an error, not a failed verification
+        assert!(key.verify_der(&z, &der[..der.len() - 1]).is_err());
+
+        // s equal to the order is well formed DER but never valid
+        let order = Secp256k1::Order.as_biguint();
+        let signature = Signature::from_biguint(BigUint::from(1u32), order).unwrap();
+        assert_eq!(key.verify_der(&z, &signature.der().unwrap()), Ok(false));
+    }
+
+    #[test]
+    fn test_network_addresses() {
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        assert_eq!(key.network(), Network::Mainnet);
+        assert_eq!(key.address().unwrap(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+        // regtest and signet share the testnet version byte
+        for network in [Network::Testnet, Network::Regtest, Network::Signet] {
+            let key = key.clone().with_network(network);
+            assert_eq!(key.network(), network);
+            assert_eq!(key.address().unwrap(), "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r");
+        }
+    }
+
+    #[test]
+    fn test_to_wif() {
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        assert_eq!(
+            key.to_wif().unwrap(),
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn"
+        );
+        for network in [Network::Testnet, Network::Regtest, Network::Signet] {
+            assert_eq!(
+                key.clone().with_network(network).to_wif().unwrap(),
+                "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA"
+            );
+        }
+    }
+
+    fn hex_array<const N: usize>(hex_str: &str) -> [u8; N] {
+        <[u8; N]>::try_from(encoding::from_hex(hex_str).unwrap().as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_verify_schnorr() {
+        // BIP340 test vector 0
+        let pubkey =
+            hex_array::<32>("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+        let msg = [0u8; 32];
+        let sig = hex_array::<64>(
+            "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+             25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+        );
+        assert!(verify_schnorr(&pubkey, &msg, &sig));
+
+        // BIP340 test vector 1
+        let pubkey =
+            hex_array::<32>("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+        let msg =
+            hex_array::<32>("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89");
+        let mut sig = hex_array::<64>(
+            "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+             8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        );
+        assert!(verify_schnorr(&pubkey, &msg, &sig));
+
+        sig[63] ^= 1;
+        assert!(!verify_schnorr(&pubkey, &msg, &sig));
+    }
+
+    #[test]
+    fn test_verify_taproot_keypath() {
+        // Key path spend generated with the BIP340/BIP341 reference code:
+        // internal secret key 0x1234, output key tweaked with `TapTweak`
+        // and no script tree, zero auxiliary randomness
+        let output_key =
+            hex_array::<32>("d699a08dbae4a347c82d5b74361b7178f626a6279e85900978952abff063eb71");
+        let sighash =
+            hex_array::<32>("1e4f10ac1a126707354f221048dd4c2643198f85030960187c78bfe1e8c35f18");
+        let sig = hex_array::<64>(
+            "a97cbda49dfb1fd09527b09c7d8a2877988b5ac26c2085e26dbbcccf9cf324ea\
+             026038562c80a1e91939518fac1dc62f1f27e7b597a060f9f0f4fc0468fb3625",
+        );
+        assert!(verify_taproot_keypath(&output_key, &sighash, &sig));
+
+        // The untweaked internal key must not verify the same signature
+        let internal_key =
+            hex_array::<32>("37a4aef1f8423ca076e4b7d99a8cabff40ddb8231f2a9f01081f15d7fa65c1ba");
+        assert!(!verify_taproot_keypath(&internal_key, &sighash, &sig));
+    }
+
+    #[test]
+    fn test_verify_taproot_keypath_bip341_vector() {
+        // BIP341 wallet-test-vectors.json, keyPathSpending input 0. The
+        // witness carries this signature followed by its sighash type byte
+        let internal_key =
+            hex_array::<32>("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d");
+        let output_key =
+            hex_array::<32>("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343");
+        let sighash =
+            hex_array::<32>("2514a6272f85cfa0f45eb907fcb0d121b808ed37c6ea160a5a9046ed5526d555");
+        let sig = hex_array::<64>(
+            "ed7c1647cb97379e76892be0cacff57ec4a7102aa24296ca39af7541246d8ff1\
+             4d38958d4cc1e2e478e4d4a764bbfd835b16d4e314b72937b29833060b87276c",
+        );
+        assert!(verify_taproot_keypath(&output_key, &sighash, &sig));
+        assert!(!verify_taproot_keypath(&internal_key, &sighash, &sig));
+
+        let mut other = sighash;
+        other[0] ^= 1;
+        assert!(!verify_taproot_keypath(&output_key, &other, &sig));
+    }
+
+    #[test]
+    fn test_rfc6979_candidates_retry() {
+        // First two RFC6979 candidates for key 1, computed independently
+        let first = BigUint::from_str_radix(
+            "aa63d6e8448f714469aea76bac95d1b1de4960095b370262929a3bd32cce86a8",
+            16,
+        )
+        .unwrap();
+        let second = BigUint::from_str_radix(
+            "4ccb63068f7b1fb01c6e24ff28468253040593e933ad3de3c896975aa81c6e1c",
+            16,
+        )
+        .unwrap();
+
+        let key = Key::from_biguint(BigUint::from(1u32)).unwrap();
+        let z = [0x42u8; 32];
+        assert_eq!(
+            BigUint::from_bytes_be(&key.deterministic_k(&z).unwrap()),
+            first
+        );
+
+        // Reject the first candidate as sign does for a zero r or s
+        let mut seen = Vec::new();
+        let signature = key
+            .rfc6979_candidates(&z, |k| {
+                seen.push(k.clone());
+                if seen.len() == 1 {
+                    None
+                } else {
+                    key.sign_with_nonce(z, k).ok()
+                }
+            })
+            .unwrap();
+
+        assert_eq!(seen, vec![first.clone(), second.clone()]);
+        assert_eq!(signature, key.sign_with_nonce(z, &second).unwrap());
+        assert_ne!(signature, key.sign_with_nonce(z, &first).unwrap());
+        assert_eq!(
+            key.sign(z).unwrap(),
+            key.sign_with_nonce(z, &first).unwrap()
+        );
+        assert!(key.verify(&z, &signature));
+    }
 }
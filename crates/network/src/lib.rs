@@ -0,0 +1,337 @@
+use encoding::{read_u32_le, read_varint, read_varstr, write_u32_le, write_varint, write_varstr};
+use hasher::double_sha256;
+use std::io::Read;
+
+/// Inventory type for a transaction
+pub const MSG_TX: u32 = 1;
+
+/// Inventory type for a full block
+pub const MSG_BLOCK: u32 = 2;
+
+/// Inventory type for a merkle block (BIP37)
+pub const MSG_FILTERED_BLOCK: u32 = 3;
+
+/// A single inventory entry: the type of the object and its hash.
+/// The hash is stored in the human readable (big-endian) order
+/// and reversed when serialized
+pub type Inventory = (u32, [u8; 32]);
+
+/// Announce the knowledge of one or more objects (blocks or transactions)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvMessage {
+    pub items: Vec<Inventory>,
+}
+
+/// Request one or more objects (blocks or transactions) from a peer
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetDataMessage {
+    pub items: Vec<Inventory>,
+}
+
+/// Serialize a list of inventory entries: the number of entries as
+/// varint followed by the type as 4 bytes little-endian and the
+/// hash in little-endian for each one
+fn serialize_inventory(items: &[Inventory]) -> Vec<u8> {
+    let mut result = write_varint(items.len() as u64);
+    for (data_type, hash) in items {
+        result.extend(write_u32_le(*data_type));
+        result.extend(hash.iter().rev());
+    }
+    result
+}
+
+/// Parse a list of inventory entries
+fn parse_inventory(reader: &mut impl Read) -> Result<Vec<Inventory>, String> {
+    let count = read_varint(reader)?;
+    let mut items = Vec::new();
+    for _ in 0..count {
+        let data_type = read_u32_le(reader)?;
+        let mut hash = [0u8; 32];
+        reader
+            .read_exact(&mut hash)
+            .map_err(|e| format!("Failed to read inventory hash: {}", e))?;
+        hash.reverse();
+        items.push((data_type, hash));
+    }
+    Ok(items)
+}
+
+impl InvMessage {
+    pub const COMMAND: &'static [u8] = b"inv";
+
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Add an object to the inventory
+    pub fn add_data(&mut self, data_type: u32, hash: [u8; 32]) {
+        self.items.push((data_type, hash));
+    }
+
+    /// Serialize the message payload
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_inventory(&self.items)
+    }
+
+    /// Parse the message payload
+    pub fn parse(reader: &mut impl Read) -> Result<Self, String> {
+        Ok(Self {
+            items: parse_inventory(reader)?,
+        })
+    }
+}
+
+impl Default for InvMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GetDataMessage {
+    pub const COMMAND: &'static [u8] = b"getdata";
+
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Add an object to be requested
+    pub fn add_data(&mut self, data_type: u32, hash: [u8; 32]) {
+        self.items.push((data_type, hash));
+    }
+
+    /// Serialize the message payload
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_inventory(&self.items)
+    }
+
+    /// Parse the message payload
+    pub fn parse(reader: &mut impl Read) -> Result<Self, String> {
+        Ok(Self {
+            items: parse_inventory(reader)?,
+        })
+    }
+}
+
+impl Default for GetDataMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Load a bloom filter (BIP37) into the connection, so the peer only
+/// relays the transactions matching it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterLoadMessage {
+    pub filter: Vec<u8>,
+    pub hash_funcs: u32,
+    pub tweak: u32,
+    pub flag: u8,
+}
+
+impl FilterLoadMessage {
+    pub const COMMAND: &'static [u8] = b"filterload";
+
+    pub fn new(filter: Vec<u8>, hash_funcs: u32, tweak: u32, flag: u8) -> Self {
+        Self {
+            filter,
+            hash_funcs,
+            tweak,
+            flag,
+        }
+    }
+
+    /// Serialize the message payload: the bit field as a varstr,
+    /// the number of hash functions, the tweak and the matched item flag
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut result = write_varstr(&self.filter);
+        result.extend(write_u32_le(self.hash_funcs));
+        result.extend(write_u32_le(self.tweak));
+        result.push(self.flag);
+        result
+    }
+}
+
+/// A block header followed by a partial merkle tree proving the
+/// inclusion of the transactions matched by a bloom filter
+///
+/// As in the book, `prev_block`, `merkle_root` and `hashes` are stored
+/// in the human readable (big-endian) order
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleBlock {
+    pub version: u32,
+    pub prev_block: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: u32,
+    pub bits: [u8; 4],
+    pub nonce: [u8; 4],
+    pub total: u32,
+    pub hashes: Vec<[u8; 32]>,
+    pub flags: Vec<u8>,
+}
+
+/// Read 32 bytes and reverse them to the human readable order
+fn read_hash(reader: &mut impl Read) -> Result<[u8; 32], String> {
+    let mut hash = [0u8; 32];
+    reader
+        .read_exact(&mut hash)
+        .map_err(|e| format!("Failed to read hash: {}", e))?;
+    hash.reverse();
+    Ok(hash)
+}
+
+/// Read 4 raw bytes
+fn read_4_bytes(reader: &mut impl Read) -> Result<[u8; 4], String> {
+    let mut bytes = [0u8; 4];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|e| format!("Failed to read 4 bytes: {}", e))?;
+    Ok(bytes)
+}
+
+impl MerkleBlock {
+    pub const COMMAND: &'static [u8] = b"merkleblock";
+
+    /// Parse the message payload
+    pub fn parse(reader: &mut impl Read) -> Result<Self, String> {
+        let version = read_u32_le(reader)?;
+        let prev_block = read_hash(reader)?;
+        let merkle_root = read_hash(reader)?;
+        let timestamp = read_u32_le(reader)?;
+        let bits = read_4_bytes(reader)?;
+        let nonce = read_4_bytes(reader)?;
+        let total = read_u32_le(reader)?;
+
+        let num_hashes = read_varint(reader)?;
+        let mut hashes = Vec::new();
+        for _ in 0..num_hashes {
+            hashes.push(read_hash(reader)?);
+        }
+
+        let flags = read_varstr(reader)?;
+
+        Ok(Self {
+            version,
+            prev_block,
+            merkle_root,
+            timestamp,
+            bits,
+            nonce,
+            total,
+            hashes,
+            flags,
+        })
+    }
+
+    /// Rebuild the merkle root from the hashes and flag bits
+    /// and compare it against the one in the header
+    pub fn is_valid(&self) -> bool {
+        if self.total == 0 {
+            return false;
+        }
+
+        // Flag bits are read from the least significant bit of each byte
+        let flag_bits: Vec<bool> = self
+            .flags
+            .iter()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .collect();
+
+        // Merkle hashes are computed in the serialization (little-endian) order
+        let hashes: Vec<[u8; 32]> = self
+            .hashes
+            .iter()
+            .map(|h| {
+                let mut le = *h;
+                le.reverse();
+                le
+            })
+            .collect();
+
+        let mut tree = PartialMerkleTree {
+            total: self.total,
+            max_depth: (self.total as f64).log2().ceil() as u32,
+            flag_bits,
+            hashes,
+            bits_used: 0,
+            hashes_used: 0,
+        };
+
+        let mut root = match tree.traverse(0, 0) {
+            Ok(root) => root,
+            Err(_) => return false,
+        };
+
+        // Every hash must be consumed and the remaining flag bits must be padding
+        if tree.hashes_used != tree.hashes.len() {
+            return false;
+        }
+        if tree.flag_bits[tree.bits_used..].iter().any(|bit| *bit) {
+            return false;
+        }
+
+        root.reverse();
+        root == self.merkle_root
+    }
+}
+
+/// Helper to walk a partial merkle tree depth-first, as described
+/// in Programming Bitcoin chapter 11
+struct PartialMerkleTree {
+    total: u32,
+    max_depth: u32,
+    flag_bits: Vec<bool>,
+    hashes: Vec<[u8; 32]>,
+    bits_used: usize,
+    hashes_used: usize,
+}
+
+impl PartialMerkleTree {
+    /// Number of nodes at a given depth
+    fn width(&self, depth: u32) -> u64 {
+        let step = 1u64 << (self.max_depth - depth);
+        (self.total as u64).div_ceil(step)
+    }
+
+    fn next_flag_bit(&mut self) -> Result<bool, String> {
+        let bit = self
+            .flag_bits
+            .get(self.bits_used)
+            .copied()
+            .ok_or("Not enough flag bits")?;
+        self.bits_used += 1;
+        Ok(bit)
+    }
+
+    fn next_hash(&mut self) -> Result<[u8; 32], String> {
+        let hash = self
+            .hashes
+            .get(self.hashes_used)
+            .copied()
+            .ok_or("Not enough hashes")?;
+        self.hashes_used += 1;
+        Ok(hash)
+    }
+
+    /// Compute the hash of the node at `depth` and `index`. A leaf, or an
+    /// internal node with flag bit 0, has its hash given; otherwise it is
+    /// the merkle parent of its children
+    fn traverse(&mut self, depth: u32, index: u64) -> Result<[u8; 32], String> {
+        let flag = self.next_flag_bit()?;
+
+        if depth == self.max_depth || !flag {
+            return self.next_hash();
+        }
+
+        let left = self.traverse(depth + 1, index * 2)?;
+        let right = if index * 2 + 1 < self.width(depth + 1) {
+            self.traverse(depth + 1, index * 2 + 1)?
+        } else {
+            left
+        };
+
+        let mut parent = Vec::with_capacity(64);
+        parent.extend_from_slice(&left);
+        parent.extend_from_slice(&right);
+        double_sha256(&parent).map_err(|e| format!("Failed to hash merkle parent: {:?}", e))
+    }
+}
@@ -0,0 +1,121 @@
+use network::{
+    FilterLoadMessage, GetDataMessage, InvMessage, MerkleBlock, MSG_BLOCK, MSG_FILTERED_BLOCK,
+    MSG_TX,
+};
+use std::io::Cursor;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash_from_hex(s: &str) -> [u8; 32] {
+        <[u8; 32]>::try_from(hex::decode(s).unwrap().as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_getdata_serialize() {
+        // Programming bitcoin chapter 12
+        let expected = hex::decode("020300000030eb2540c41025690160a1014c577061596e32e426b712c7ca00000000000000030000001049847939585b0652fba793661c361223446b6fc41089b8be00000000000000").unwrap();
+
+        let mut getdata = GetDataMessage::new();
+        getdata.add_data(
+            MSG_FILTERED_BLOCK,
+            hash_from_hex("00000000000000cac712b726e4326e596170574c01a16001692510c44025eb30"),
+        );
+        getdata.add_data(
+            MSG_FILTERED_BLOCK,
+            hash_from_hex("00000000000000beb88910c46f6b442312361c6693a7fb52065b583979844910"),
+        );
+
+        assert_eq!(getdata.serialize(), expected);
+    }
+
+    #[test]
+    fn test_getdata_round_trip_filtered_block() {
+        let mut getdata = GetDataMessage::new();
+        getdata.add_data(
+            MSG_FILTERED_BLOCK,
+            hash_from_hex("00000000000000cac712b726e4326e596170574c01a16001692510c44025eb30"),
+        );
+
+        let serialized = getdata.serialize();
+        assert_eq!(serialized.len(), 1 + 4 + 32);
+
+        let mut cursor = Cursor::new(serialized);
+        let parsed = GetDataMessage::parse(&mut cursor).unwrap();
+        assert_eq!(parsed, getdata);
+    }
+
+    #[test]
+    fn test_inv_round_trip() {
+        let mut inv = InvMessage::new();
+        inv.add_data(
+            MSG_TX,
+            hash_from_hex("d1c789a9c60383bf715f3f6ad9d14b91fe55f3deb369fe5d9280cb1a01793f81"),
+        );
+        inv.add_data(
+            MSG_BLOCK,
+            hash_from_hex("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"),
+        );
+
+        let mut cursor = Cursor::new(inv.serialize());
+        let parsed = InvMessage::parse(&mut cursor).unwrap();
+        assert_eq!(parsed, inv);
+    }
+
+    #[test]
+    fn test_parse_fail_truncated() {
+        let mut cursor = Cursor::new(vec![1u8, 2u8, 0u8, 0u8, 0u8, 1u8]);
+        assert!(InvMessage::parse(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_filterload_serialize() {
+        // Programming bitcoin chapter 12: BloomFilter(10, 5, 99)
+        // with b"Hello World" and b"Goodbye!" added
+        let filter = hex::decode("4000600a080000010940").unwrap();
+        let filterload = FilterLoadMessage::new(filter, 5, 99, 1);
+        let expected = hex::decode("0a4000600a080000010940050000006300000001").unwrap();
+        assert_eq!(filterload.serialize(), expected);
+    }
+
+    #[test]
+    fn test_merkleblock_parse() {
+        // Programming bitcoin chapter 11
+        let raw = hex::decode("00000020df3b053dc46f162a9b00c7f0d5124e2676d47bbe7c5d0793a500000000000000ef445fef2ed495c275892206ca533e7411907971013ab83e3b47bd0d692d14d4dc7c835b67d8001ac157e670bf0d00000aba412a0d1480e370173072c9562becffe87aa661c1e4a6dbc305d38ec5dc088a7cf92e6458aca7b32edae818f9c2c98c37e06bf72ae0ce80649a38655ee1e27d34d9421d940b16732f24b94023e9d572a7f9ab8023434a4feb532d2adfc8c2c2158785d1bd04eb99df2e86c54bc13e139862897217400def5d72c280222c4cbaee7261831e1550dbb8fa82853e9fe506fc5fda3f7b919d8fe74b6282f92763cef8e625f977af7c8619c32a369b832bc2d051ecd9c73c51e76370ceabd4f25097c256597fa898d404ed53425de608ac6bfe426f6e2bb457f1c554866eb69dcb8d6bf6f880e9a59b3cd053e6c7060eeacaacf4dac6697dac20e4bd3f38a2ea2543d1ab7953e3430790a9f81e1c67f5b58c825acf46bd02848384eebe9af917274cdfbb1a28a5d58a23a17977def0de10d644258d9c54f886d47d293a411cb6226103b55635").unwrap();
+
+        let mut cursor = Cursor::new(raw);
+        let mb = MerkleBlock::parse(&mut cursor).unwrap();
+
+        assert_eq!(mb.version, 0x20000000);
+        assert_eq!(
+            mb.merkle_root,
+            hash_from_hex("d4142d690dbd473b3eb83a0171799011743e53ca06228975c295d42eef5f44ef")
+        );
+        assert_eq!(
+            mb.prev_block,
+            hash_from_hex("00000000000000a593075d7cbe7bd476264e12d5f0c7009b2a166fc43d053bdf")
+        );
+        assert_eq!(mb.timestamp, 0x5b837cdc);
+        assert_eq!(mb.bits, [0x67u8, 0xd8u8, 0x00u8, 0x1au8]);
+        assert_eq!(mb.nonce, [0xc1u8, 0x57u8, 0xe6u8, 0x70u8]);
+        assert_eq!(mb.total, 3519);
+        assert_eq!(mb.hashes.len(), 10);
+        assert_eq!(
+            mb.hashes[0],
+            hash_from_hex("8a08dcc58ed305c3dba6e4c161a67ae8ffec2b56c972301770e380140d2a41ba")
+        );
+        assert_eq!(mb.flags, vec![0xb5u8, 0x56u8, 0x35u8]);
+        assert!(mb.is_valid());
+    }
+
+    #[test]
+    fn test_merkleblock_is_valid_fail_tampered_hash() {
+        let raw = hex::decode("00000020df3b053dc46f162a9b00c7f0d5124e2676d47bbe7c5d0793a500000000000000ef445fef2ed495c275892206ca533e7411907971013ab83e3b47bd0d692d14d4dc7c835b67d8001ac157e670bf0d00000aba412a0d1480e370173072c9562becffe87aa661c1e4a6dbc305d38ec5dc088a7cf92e6458aca7b32edae818f9c2c98c37e06bf72ae0ce80649a38655ee1e27d34d9421d940b16732f24b94023e9d572a7f9ab8023434a4feb532d2adfc8c2c2158785d1bd04eb99df2e86c54bc13e139862897217400def5d72c280222c4cbaee7261831e1550dbb8fa82853e9fe506fc5fda3f7b919d8fe74b6282f92763cef8e625f977af7c8619c32a369b832bc2d051ecd9c73c51e76370ceabd4f25097c256597fa898d404ed53425de608ac6bfe426f6e2bb457f1c554866eb69dcb8d6bf6f880e9a59b3cd053e6c7060eeacaacf4dac6697dac20e4bd3f38a2ea2543d1ab7953e3430790a9f81e1c67f5b58c825acf46bd02848384eebe9af917274cdfbb1a28a5d58a23a17977def0de10d644258d9c54f886d47d293a411cb6226103b55635").unwrap();
+
+        let mut cursor = Cursor::new(raw);
+        let mut mb = MerkleBlock::parse(&mut cursor).unwrap();
+        mb.hashes[3][0] ^= 1u8;
+        assert!(!mb.is_valid());
+    }
+}
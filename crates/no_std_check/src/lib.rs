@@ -0,0 +1,26 @@
+/*
+ * Exercise the field, curve and hash arithmetic from a no_std crate
+ */
+#![no_std]
+
+use field_element::FieldElement;
+use num_bigint::BigUint;
+use secp256k1::{Secp256k1, Secp256k1Point, PRIME};
+
+/// Compute (a + b) * a in the secp256k1 field
+pub fn field_arithmetic(a: &str, b: &str) -> FieldElement {
+    let fe_a = FieldElement::new(a, PRIME).unwrap();
+    let fe_b = FieldElement::new(b, PRIME).unwrap();
+    &(&fe_a + &fe_b) * &fe_a
+}
+
+/// Compute k * G
+pub fn point_arithmetic(k: u64) -> Secp256k1Point {
+    let g = Secp256k1::Generator.as_point();
+    &g * &BigUint::from(k)
+}
+
+/// Compute hash256 of a message
+pub fn hash_arithmetic(message: &[u8]) -> [u8; 32] {
+    hasher::double_sha256(message).unwrap()
+}
@@ -0,0 +1,26 @@
+use no_std_check::{field_arithmetic, hash_arithmetic, point_arithmetic};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_arithmetic() {
+        let result = field_arithmetic("2", "3");
+        assert_eq!(result.num, 10u32.into());
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = point_arithmetic(3);
+        let sec = p.to_compressed_sec().unwrap();
+        assert_eq!(sec[0], 2u8);
+        assert_eq!(sec[1..5], [249u8, 48u8, 138u8, 1u8]);
+    }
+
+    #[test]
+    fn test_hash_arithmetic() {
+        let hash = hash_arithmetic(b"Hello, world");
+        assert_eq!(hash[..4], [217u8, 235u8, 182u8, 14u8]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// Define the `OpCode` enum together with its byte value and
+/// its canonical name, so the three never get out of sync
+macro_rules! opcodes {
+    ($($variant:ident = $byte:expr, $name:expr;)*) => {
+        /// Script operations (see Programming Bitcoin chapter 6)
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum OpCode {
+            $($variant,)*
+        }
+
+        impl OpCode {
+            /// Get the operation represented by a byte, if it is a known one
+            pub fn from_byte(byte: u8) -> Option<Self> {
+                match byte {
+                    $($byte => Some(OpCode::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// Get the byte that represents the operation in a serialized script
+            pub fn to_byte(&self) -> u8 {
+                match self {
+                    $(OpCode::$variant => $byte,)*
+                }
+            }
+
+            /// Get the canonical name of the operation, e.g. `OP_DUP`
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(OpCode::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    Op0 = 0x00, "OP_0";
+    PushData1 = 0x4c, "OP_PUSHDATA1";
+    PushData2 = 0x4d, "OP_PUSHDATA2";
+    PushData4 = 0x4e, "OP_PUSHDATA4";
+    Op1Negate = 0x4f, "OP_1NEGATE";
+    Op1 = 0x51, "OP_1";
+    Op2 = 0x52, "OP_2";
+    Op3 = 0x53, "OP_3";
+    Op4 = 0x54, "OP_4";
+    Op5 = 0x55, "OP_5";
+    Op6 = 0x56, "OP_6";
+    Op7 = 0x57, "OP_7";
+    Op8 = 0x58, "OP_8";
+    Op9 = 0x59, "OP_9";
+    Op10 = 0x5a, "OP_10";
+    Op11 = 0x5b, "OP_11";
+    Op12 = 0x5c, "OP_12";
+    Op13 = 0x5d, "OP_13";
+    Op14 = 0x5e, "OP_14";
+    Op15 = 0x5f, "OP_15";
+    Op16 = 0x60, "OP_16";
+    Nop = 0x61, "OP_NOP";
+    If = 0x63, "OP_IF";
+    NotIf = 0x64, "OP_NOTIF";
+    Else = 0x67, "OP_ELSE";
+    EndIf = 0x68, "OP_ENDIF";
+    Verify = 0x69, "OP_VERIFY";
+    Return = 0x6a, "OP_RETURN";
+    Drop = 0x75, "OP_DROP";
+    Dup = 0x76, "OP_DUP";
+    Equal = 0x87, "OP_EQUAL";
+    EqualVerify = 0x88, "OP_EQUALVERIFY";
+    Add = 0x93, "OP_ADD";
+    Sub = 0x94, "OP_SUB";
+    Ripemd160 = 0xa6, "OP_RIPEMD160";
+    Sha256 = 0xa8, "OP_SHA256";
+    Hash160 = 0xa9, "OP_HASH160";
+    Hash256 = 0xaa, "OP_HASH256";
+    CheckSig = 0xac, "OP_CHECKSIG";
+    CheckSigVerify = 0xad, "OP_CHECKSIGVERIFY";
+    CheckMultiSig = 0xae, "OP_CHECKMULTISIG";
+    CheckMultiSigVerify = 0xaf, "OP_CHECKMULTISIGVERIFY";
+    CheckLockTimeVerify = 0xb1, "OP_CHECKLOCKTIMEVERIFY";
+    CheckSequenceVerify = 0xb2, "OP_CHECKSEQUENCEVERIFY";
+}
+
+/// Implement Display trait to print the canonical opcode name
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Encode a number with the minimal little-endian sign-magnitude
+/// encoding used by script (CScriptNum)
+pub fn encode_num(n: i64) -> Vec<u8> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let negative = n < 0;
+    let mut abs = n.unsigned_abs();
+    let mut result = Vec::new();
+
+    while abs > 0 {
+        result.push((abs & 0xff) as u8);
+        abs >>= 8;
+    }
+
+    // If the top bit is already set, the sign goes in an extra byte;
+    // otherwise it is set in the most significant byte
+    let last = result.len() - 1;
+    if result[last] & 0x80 != 0 {
+        result.push(if negative { 0x80u8 } else { 0u8 });
+    } else if negative {
+        result[last] |= 0x80;
+    }
+
+    result
+}
+
+/// Longest number accepted by `decode_num`, as for the arithmetic
+/// opcodes (CScriptNum's default `nMaxNumSize`)
+pub const MAX_NUM_SIZE: usize = 4;
+
+/// Decode a number encoded with the script (CScriptNum) rules,
+/// rejecting encodings longer than `MAX_NUM_SIZE` bytes
+pub fn decode_num(bytes: &[u8]) -> Result<i64, String> {
+    decode_num_with_max_size(bytes, MAX_NUM_SIZE)
+}
+
+/// Decode a number encoded with the script (CScriptNum) rules,
+/// rejecting encodings longer than `max_size` bytes (OP_CHECKLOCKTIMEVERIFY
+/// uses 5). Anything over 8 bytes is rejected since it does not fit an `i64`
+pub fn decode_num_with_max_size(bytes: &[u8], max_size: usize) -> Result<i64, String> {
+    if bytes.len() > max_size.min(8) {
+        return Err(format!(
+            "Script number is {} bytes long, at most {} allowed",
+            bytes.len(),
+            max_size.min(8)
+        ));
+    }
+    if bytes.is_empty() {
+        return Ok(0);
+    }
+
+    // Read as big-endian, taking the sign from the top bit of the last byte
+    let last = bytes.len() - 1;
+    let negative = bytes[last] & 0x80 != 0;
+    let mut result = (bytes[last] & 0x7f) as i64;
+
+    for byte in bytes[..last].iter().rev() {
+        result = (result << 8) | *byte as i64;
+    }
+
+    if negative {
+        Ok(-result)
+    } else {
+        Ok(result)
+    }
+}
@@ -0,0 +1,102 @@
+use script::{decode_num, decode_num_with_max_size, encode_num, OpCode, MAX_NUM_SIZE};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opcode_from_byte() {
+        assert_eq!(OpCode::from_byte(0x76u8), Some(OpCode::Dup));
+        assert_eq!(OpCode::from_byte(0xa9u8), Some(OpCode::Hash160));
+        assert_eq!(OpCode::from_byte(0xffu8), None);
+    }
+
+    #[test]
+    fn test_opcode_round_trip() {
+        for byte in 0u8..=255u8 {
+            if let Some(op) = OpCode::from_byte(byte) {
+                assert_eq!(op.to_byte(), byte);
+            }
+        }
+    }
+
+    #[test]
+    fn test_opcode_small_numbers() {
+        assert_eq!(OpCode::from_byte(0x00u8), Some(OpCode::Op0));
+        assert_eq!(OpCode::from_byte(0x51u8), Some(OpCode::Op1));
+        assert_eq!(OpCode::from_byte(0x60u8), Some(OpCode::Op16));
+    }
+
+    #[test]
+    fn test_opcode_display_p2pkh() {
+        // OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG
+        let names: Vec<String> = [0x76u8, 0xa9u8, 0x88u8, 0xacu8]
+            .iter()
+            .map(|b| format!("{}", OpCode::from_byte(*b).unwrap()))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec!["OP_DUP", "OP_HASH160", "OP_EQUALVERIFY", "OP_CHECKSIG"]
+        );
+    }
+
+    #[test]
+    fn test_encode_num() {
+        assert_eq!(encode_num(0), Vec::<u8>::new());
+        assert_eq!(encode_num(127), vec![0x7fu8]);
+        assert_eq!(encode_num(128), vec![0x80u8, 0x00u8]);
+        assert_eq!(encode_num(-1), vec![0x81u8]);
+        assert_eq!(encode_num(-128), vec![0x80u8, 0x80u8]);
+        assert_eq!(encode_num(500000), vec![0x20u8, 0xa1u8, 0x07u8]);
+        assert_eq!(
+            encode_num(i64::MAX),
+            vec![0xffu8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]
+        );
+        // the magnitude of i64::MIN needs the top bit, so the sign
+        // goes in an extra byte
+        assert_eq!(
+            encode_num(i64::MIN),
+            vec![0x00u8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x80]
+        );
+    }
+
+    #[test]
+    fn test_decode_num() {
+        assert_eq!(decode_num(&[]).unwrap(), 0);
+        assert_eq!(decode_num(&[0x7fu8]).unwrap(), 127);
+        assert_eq!(decode_num(&[0x80u8, 0x00u8]).unwrap(), 128);
+        assert_eq!(decode_num(&[0x81u8]).unwrap(), -1);
+        assert_eq!(decode_num(&[0x80u8, 0x80u8]).unwrap(), -128);
+        assert_eq!(decode_num(&[0x20u8, 0xa1u8, 0x07u8]).unwrap(), 500000);
+    }
+
+    #[test]
+    fn test_decode_num_too_long() {
+        assert_eq!(decode_num(&[0xffu8, 0xff, 0xff, 0x7f]).unwrap(), 2147483647);
+        assert_eq!(
+            decode_num(&[0xffu8, 0xff, 0xff, 0xff]).unwrap(),
+            -2147483647
+        );
+        assert!(decode_num(&[0x00u8, 0x00, 0x00, 0x80, 0x00]).is_err());
+
+        // a larger size can be allowed, but never past what fits an i64
+        let five = [0x00u8, 0x00, 0x00, 0x80, 0x00];
+        assert_eq!(decode_num_with_max_size(&five, 5).unwrap(), 2147483648);
+        assert!(decode_num_with_max_size(&five, MAX_NUM_SIZE).is_err());
+        assert!(decode_num_with_max_size(&encode_num(i64::MIN), 9).is_err());
+        assert_eq!(
+            decode_num_with_max_size(&encode_num(i64::MAX), 8).unwrap(),
+            i64::MAX
+        );
+    }
+
+    #[test]
+    fn test_num_round_trip() {
+        for n in [
+            0i64, 1, -1, 127, -127, 128, -255, 256, 65535, -65536, 2147483647,
+        ] {
+            assert_eq!(decode_num(&encode_num(n)).unwrap(), n);
+        }
+    }
+}
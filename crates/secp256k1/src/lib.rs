@@ -1,20 +1,35 @@
-use field_element::FieldElement;
-use num_bigint::{BigInt, BigUint};
-use num_integer::Integer;
-use num_traits::{Num, One, Zero};
-use std::io::{Cursor, Read};
-use std::{
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::{
     array::TryFromSliceError,
-    ops::{Add, Mul},
+    hash::{Hash, Hasher},
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Sub},
 };
+use field_element::FieldElement;
+#[cfg(feature = "std")]
+use hasher::{hash160, MAINNET_PREFIX, TESTNET_PREFIX};
+use num_bigint::BigUint;
+use num_traits::{Num, ToPrimitive, Zero};
 
 pub const PRIME: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F";
 pub const ORDER: &str = "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141";
 
+/// The coordinates are private so that a point is always either the point
+/// at infinity or has both of them set; read them with `x()` and `y()`
 #[derive(Debug, Clone)]
 pub struct Secp256k1Point {
-    pub x: Option<FieldElement>,
-    pub y: Option<FieldElement>,
+    x: Option<FieldElement>,
+    y: Option<FieldElement>,
 }
 
 pub enum Secp256k1 {
@@ -42,131 +57,436 @@ pub enum Secp256k1 {
 impl Secp256k1Point {
     #[allow(dead_code)]
     pub fn new(x: Option<FieldElement>, y: Option<FieldElement>) -> Result<Self, String> {
-        // Point at inifinity
-        if x.is_none() && y.is_none() {
-            Ok(Self { x: None, y: None })
-        } else if x.is_none() || y.is_none() {
-            return Err("Both x and y must be provided, or none for point at infinity".to_string());
+        match (x, y) {
+            // Point at inifinity
+            (None, None) => Ok(Self::infinity()),
+            (Some(x), Some(y)) => {
+                // Also rejects coordinates from another field
+                let point = Self::from_coords(x, y);
+                if point.is_on_curve() {
+                    Ok(point)
+                } else {
+                    Err(format!(
+                        "Invalid secp256k1 point:x = {:?}, y = {:?}",
+                        &point.x, &point.y
+                    ))
+                }
+            }
+            _ => Err("Both x and y must be provided, or none for point at infinity".to_string()),
+        }
+    }
+
+    /// The point at infinity, the identity of the group
+    pub const fn infinity() -> Self {
+        Self { x: None, y: None }
+    }
+
+    /// Build an affine point from both of its coordinates, without
+    /// checking the curve equation. Only for results of the group law and
+    /// already validated coordinates; the public constructors are `new`,
+    /// `deserialize` and `from_raw_xy`
+    fn from_coords(x: FieldElement, y: FieldElement) -> Self {
+        Self {
+            x: Some(x),
+            y: Some(y),
+        }
+    }
+
+    /// The x coordinate, `None` for the point at infinity
+    pub fn x(&self) -> Option<&FieldElement> {
+        self.x.as_ref()
+    }
+
+    /// The y coordinate, `None` for the point at infinity
+    pub fn y(&self) -> Option<&FieldElement> {
+        self.y.as_ref()
+    }
+
+    /// Check that the point is either the point at infinity (both
+    /// coordinates unset) or has both coordinates set
+    pub fn infinity_check(&self) -> bool {
+        self.x.is_some() == self.y.is_some()
+    }
+
+    /// Check for y**2 == x**3 + 7
+    fn satisfies_curve(x: &FieldElement, y: &FieldElement) -> bool {
+        let seven = FieldElement::from_biguint(BigUint::from(7u8), x.shared_prime()).unwrap();
+
+        let lhs = y.square(); // y**2
+        let rhs = x.pow_u32(3) + seven; // x**3 + 7
+
+        lhs == rhs
+    }
+
+    /// Check if the point lies on the curve. The point at infinity is
+    /// on the curve
+    pub fn is_on_curve(&self) -> bool {
+        match (&self.x, &self.y) {
+            (None, None) => true,
+            (Some(x), Some(y)) => {
+                let prime = Secp256k1::Prime.as_biguint();
+                x.prime() == &prime && y.prime() == &prime && Self::satisfies_curve(x, y)
+            }
+            _ => false,
+        }
+    }
+
+    /// Return an address string (P2PKH format) of the point used as
+    /// a public key: SEC, then hash160, then base58check
+    #[cfg(feature = "std")]
+    pub fn to_p2pkh_address(&self, compressed: bool, testnet: bool) -> Result<String, String> {
+        let sec = self.serialize(compressed)?;
+        let h160 = hash160(&sec).map_err(|e| format!("Failed to hash public key: {:?}", e))?;
+        let prefix = if testnet {
+            TESTNET_PREFIX
         } else {
-            // check for y**2 == x**3 + 7
-            let two = BigInt::from(2u32);
-            let three = BigInt::from(3u32);
-            let seven = FieldElement::new("7", PRIME).unwrap();
+            MAINNET_PREFIX
+        };
+        base58::encode_base58check_versioned(prefix, &h160)
+            .map_err(|e| format!("Failed to encode address: {:?}", e))
+    }
+
+    /// Parity of the y coordinate, as used by taproot and the compressed
+    /// SEC prefix. `None` for the point at infinity
+    pub fn y_is_even(&self) -> Option<bool> {
+        self.y.as_ref().map(|y| y.is_even())
+    }
 
-            let _x = x.as_ref().unwrap();
-            let _y = y.as_ref().unwrap();
+    /// Add public key points together, e.g. for key aggregation.
+    /// Fails if the sum is the point at infinity
+    pub fn combine(points: &[Secp256k1Point]) -> Result<Secp256k1Point, String> {
+        let sum: Secp256k1Point = points.iter().sum();
+        if sum.x.is_none() {
+            return Err("Combined public key is the point at infinity".to_string());
+        }
+        Ok(sum)
+    }
 
-            let lhs = _y.pow(&two); // y**2
-            let rhs = _x.pow(&three) + seven; // x**3 + 7
+    /// The generator point G, built once and cached
+    #[cfg(feature = "std")]
+    pub fn generator() -> &'static Secp256k1Point {
+        static GENERATOR: std::sync::OnceLock<Secp256k1Point> = std::sync::OnceLock::new();
+        GENERATOR.get_or_init(|| Secp256k1::Generator.as_point())
+    }
 
-            if lhs == rhs {
-                Ok(Self { x, y })
-            } else {
-                Err(format!(
-                    "Invalid secp256k1 point:x = {:?}, y = {:?}",
-                    &x, &y
-                ))
+    /// Check if the point is a valid public key, i.e., a point
+    /// on the curve, different from infinity and that belongs to
+    /// the group generated by G (order * self == infinity)
+    pub fn is_valid_public_key(&self) -> bool {
+        match (&self.x, &self.y) {
+            (Some(_), Some(_)) => {
+                // Checks the field prime before the curve equation
+                if !self.is_on_curve() {
+                    return false;
+                }
+                // Do not reduce the scalar here, otherwise the check is trivially true
+                let order = Secp256k1::Order.as_biguint();
+                let result = double_and_add(self, &order);
+                result.x.is_none() && result.y.is_none()
             }
+            _ => false,
+        }
+    }
+
+    /// Multiply the point by a scalar using the Montgomery ladder
+    ///
+    /// The scalar is replaced by `k + n` or `k + 2n`, whichever has bit 256
+    /// set, so the ladder always walks the same 256 bits and starts from
+    /// `P` and `2P` instead of the point at infinity. Each step does one
+    /// addition and one doubling, and the bit only selects which
+    /// accumulator receives which result by indexing, without branching.
+    /// Should be used when `k` is a private key or a nonce.
+    ///
+    /// This is not constant-time: the `BigUint` arithmetic takes time
+    /// depending on the values, the accumulator selected by each bit is a
+    /// secret-dependent memory access, and the special cases of `Add`
+    /// (infinity, equal or opposite operands) are still reached for
+    /// the rare scalars whose partial multiples hit them.
+    pub fn mul_ct(&self, k: &BigUint) -> Secp256k1Point {
+        if self.x.is_none() {
+            return Secp256k1Point::infinity();
+        }
+
+        let order = Secp256k1::Order.as_biguint();
+        let k = k % &order;
+        // k + n < 2^257 and, when it is below 2^256, k + 2n has bit 256 set
+        let padded = [&k + &order * 2u32, &k + &order];
+        let k = &padded[padded[1].bit(256) as usize];
+
+        let mut ladder = [self.clone(), self.double()];
+        for i in (0..256u64).rev() {
+            let bit = k.bit(i) as usize;
+            let sum = &ladder[0] + &ladder[1];
+            ladder[bit] = ladder[bit].double();
+            ladder[1 - bit] = sum;
+            debug_assert!(ladder[0].infinity_check() && ladder[1].infinity_check());
+        }
+        let [result, _] = ladder;
+        result
+    }
+
+    /// Compute `self + self` with the tangent formula directly, skipping
+    /// the branches of the `Add` implementation
+    pub fn double(&self) -> Secp256k1Point {
+        let (x1, y1) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return Secp256k1Point::infinity(),
+        };
+
+        // Tangent at y == 0 is Point at infinity
+        if y1.num.is_zero() {
+            return Secp256k1Point::infinity();
         }
+
+        // Compute slope: s = (3 * x1^2) / (2 * y1), with additions
+        // instead of building the constants in the field
+        let x1_squared = x1.square();
+        let numerator = &(&x1_squared + &x1_squared) + &x1_squared;
+        let denominator = y1 + y1;
+        let s = &numerator / &denominator;
+
+        // Compute x3: x3 = s^2 - 2 * x1
+        let s2 = s.square();
+        let x3 = &s2 - &(x1 + x1);
+
+        // Compute y3: y3 = s * (x1 - x3) - y1
+        let x1_minus_x3 = x1 - &x3;
+        let y3 = &(&s * &x1_minus_x3) - y1;
+
+        Secp256k1Point::from_coords(x3, y3)
+    }
+
+    /// Multiply the point by a scalar using its windowed non-adjacent form (wNAF)
+    ///
+    /// The odd multiples `P, 3P, 5P, ..., (2^(w-1) - 1)P` are precomputed, and
+    /// since the wNAF has at most one non-zero digit in any `window` consecutive
+    /// digits, far fewer additions are needed than with double-and-add.
+    /// Returns an error if `window` is not between 2 and 8.
+    pub fn mul_wnaf(&self, k: &BigUint, window: usize) -> Result<Secp256k1Point, String> {
+        if !(2..=8).contains(&window) {
+            return Err(format!(
+                "wNAF window must be between 2 and 8, got {}",
+                window
+            ));
+        }
+
+        let k = k % Secp256k1::Order.as_biguint();
+        if self.x.is_none() || k.is_zero() {
+            return Ok(Secp256k1Point::infinity());
+        }
+
+        // Precompute the odd multiples of the point
+        let double = self.double();
+        let mut table = vec![self.clone()];
+        for i in 1..(1usize << (window - 2)) {
+            let next = &table[i - 1] + &double;
+            table.push(next);
+        }
+
+        let mut result = Secp256k1Point::infinity();
+        for digit in wnaf(&k, window).iter().rev() {
+            result = result.double();
+            if *digit > 0 {
+                result += &table[(*digit as usize - 1) / 2];
+            } else if *digit < 0 {
+                let point = &table[(digit.unsigned_abs() as usize - 1) / 2];
+                result += &point.negate();
+            }
+            debug_assert!(result.infinity_check());
+        }
+        Ok(result)
+    }
+
+    /// Return the point with the y coordinate negated, i.e. `-P`.
+    /// The point at infinity is its own negation
+    pub fn negate(&self) -> Secp256k1Point {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => Secp256k1Point::from_coords(x.clone(), -y),
+            _ => Secp256k1Point::infinity(),
+        }
+    }
+
+    /// Multiply the point by a small scalar
+    pub fn mul_u64(&self, k: u64) -> Secp256k1Point {
+        scalar_mul(self, &BigUint::from(k))
+    }
+
+    /// Compute `u * g + v * p` with Shamir's trick
+    ///
+    /// Both scalars are walked together from the most significant bit, so
+    /// the doublings are shared and each step adds `g`, `p` or the
+    /// precomputed `g + p` at most once.
+    pub fn mul_add(
+        u: &BigUint,
+        g: &Secp256k1Point,
+        v: &BigUint,
+        p: &Secp256k1Point,
+    ) -> Secp256k1Point {
+        let order = Secp256k1::Order.as_biguint();
+        let u = u % &order;
+        let v = v % &order;
+        let g_plus_p = g + p;
+
+        let mut result = Secp256k1Point::infinity();
+        for i in (0..u.bits().max(v.bits())).rev() {
+            result = result.double();
+            match (u.bit(i), v.bit(i)) {
+                (true, true) => result += &g_plus_p,
+                (true, false) => result += g,
+                (false, true) => result += p,
+                (false, false) => {}
+            }
+            debug_assert!(result.infinity_check());
+        }
+        result
     }
 
     /// Binary version of uncompressed SEC format
     pub fn to_uncompressed_sec(&self) -> Result<[u8; 65], TryFromSliceError> {
         let mut serialized = vec![4u8];
-        serialized.extend(self.x.as_ref().unwrap().num.to_bytes_be());
-        serialized.extend(self.y.as_ref().unwrap().num.to_bytes_be());
+        serialized.extend(self.x.as_ref().unwrap().to_32_bytes_be());
+        serialized.extend(self.y.as_ref().unwrap().to_32_bytes_be());
         <[u8; 65]>::try_from(serialized.as_slice())
     }
 
     /// Binary version of compressed SEC format
     pub fn to_compressed_sec(&self) -> Result<[u8; 33], TryFromSliceError> {
-        let y = &self.y.as_ref().unwrap().num;
-        let two = BigUint::from(2u32);
-        let zero = BigUint::from(0u32);
-
-        let mut serialized = if y % two == zero {
+        let mut serialized = if self.y.as_ref().unwrap().is_even() {
             vec![2u8]
         } else {
             vec![3u8]
         };
 
-        serialized.extend(self.x.as_ref().unwrap().num.to_bytes_be());
+        serialized.extend(self.x.as_ref().unwrap().to_32_bytes_be());
         <[u8; 33]>::try_from(serialized.as_slice())
     }
 
-    /// Desserialize a vector of bytes to a point
-    pub fn deserialize(sec: Vec<u8>) -> Result<Secp256k1Point, String> {
-        let mut cursor = Cursor::new(sec);
-        let mut sec_type = [0u8; 1];
-        let mut x = [0u8; 32];
+    /// Serialize the point in SEC format, compressed or not.
+    /// The point at infinity has no SEC representation
+    pub fn serialize(&self, compressed: bool) -> Result<Vec<u8>, String> {
+        if self.x.is_none() || self.y.is_none() {
+            return Err("Cannot serialize the point at infinity".to_string());
+        }
 
-        cursor.read_exact(&mut sec_type).unwrap();
-        cursor.read_exact(&mut x).unwrap();
+        if compressed {
+            self.to_compressed_sec()
+                .map(|sec| sec.to_vec())
+                .map_err(|e| format!("Failed to serialize compressed SEC: {:?}", e))
+        } else {
+            self.to_uncompressed_sec()
+                .map(|sec| sec.to_vec())
+                .map_err(|e| format!("Failed to serialize uncompressed SEC: {:?}", e))
+        }
+    }
 
-        let fe_x = FieldElement {
-            num: BigUint::from_bytes_be(x.as_slice()),
-            prime: Secp256k1::Prime.as_biguint(),
+    /// Desserialize a vector of bytes to a point
+    pub fn deserialize(sec: Vec<u8>) -> Result<Secp256k1Point, String> {
+        let (sec_type, data) = sec.split_first().ok_or("Cannot deserialize an empty SEC")?;
+
+        // Compressed SEC is 33 bytes long; uncompressed and legacy hybrid
+        // (0x06 for an even y, 0x07 for an odd y) ones are 65 bytes long
+        let expected_len = match *sec_type {
+            2u8 | 3u8 => 33,
+            4u8 | 6u8 | 7u8 => 65,
+            _ => return Err(format!("Unknown SEC prefix 0x{:02x}", sec_type)),
         };
+        if sec.len() != expected_len {
+            return Err(format!(
+                "SEC with prefix 0x{:02x} must be {} bytes long, got {}",
+                sec_type,
+                expected_len,
+                sec.len()
+            ));
+        }
 
-        // Deserialize a uncompressed SEC formated point
-        if sec_type[0] == 4u8 {
-            let mut y = [0u8; 32];
-            cursor.read_exact(&mut y).unwrap();
+        let fe_x = coordinate(&data[..32])?;
 
-            let fe_y = FieldElement {
-                num: BigUint::from_bytes_be(y.as_slice()),
-                prime: Secp256k1::Prime.as_biguint(),
-            };
+        // Deserialize a uncompressed or hybrid SEC formated point
+        if expected_len == 65 {
+            let fe_y = coordinate(&data[32..])?;
 
-            return Ok(Secp256k1Point {
-                x: Some(fe_x),
-                y: Some(fe_y),
-            });
+            if *sec_type != 4u8 && fe_y.is_even() != (*sec_type == 6u8) {
+                return Err(format!(
+                    "Hybrid SEC prefix 0x{:02x} does not match the y parity",
+                    sec_type
+                ));
+            }
+
+            // Enforce y^2 == x^3 + 7 on the raw coordinates
+            return Secp256k1Point::new(Some(fe_x), Some(fe_y));
         }
 
         // Deserialize a compressed SEC formated point
-        let is_even = sec_type[0] == 2u8;
-        let fe_7 = FieldElement {
-            num: BigUint::from(7u8),
-            prime: Secp256k1::Prime.as_biguint(),
-        };
+        let is_even = *sec_type == 2u8;
+        let fe_7 = FieldElement::from_biguint(BigUint::from(7u8), fe_x.shared_prime())?;
+
+        let alpha_fe = fe_x.pow_u32(3) + fe_7;
+        let (even, odd) = alpha_fe
+            .sqrt_both()
+            .map_err(|_| "x coordinate is not on the secp256k1 curve".to_string())?;
+
+        // sqrt_both only returns verified roots, check the recovered
+        // point against the curve equation all the same
+        let y = if is_even { even } else { odd };
+        Secp256k1Point::new(Some(fe_x), Some(y))
+    }
 
-        let alpha_fe = fe_x.pow(&BigInt::from(3u8)) + fe_7;
-        let beta_fe = alpha_fe.sqrt();
+    /// Lift an x-only public key (BIP340) to the curve point with
+    /// that x coordinate and an even y coordinate
+    pub fn lift_x(x: &[u8; 32]) -> Result<Self, String> {
+        let fe_x = coordinate(x)?;
+        let fe_7 = FieldElement::from_biguint(BigUint::from(7u8), fe_x.shared_prime())?;
 
-        let prime = Secp256k1::Prime.as_biguint();
+        let c = fe_x.pow_u32(3) + fe_7;
+        let (y, _) = c
+            .sqrt_both()
+            .map_err(|_| "x coordinate is not on the secp256k1 curve".to_string())?;
 
-        if is_even == beta_fe.num.is_even() {
-            Ok(Secp256k1Point {
-                x: Some(fe_x),
-                y: Some(beta_fe),
-            })
-        } else {
-            let odd = FieldElement {
-                num: &prime - &beta_fe.num,
-                prime: prime.clone(),
-            };
-            Ok(Secp256k1Point {
-                x: Some(fe_x),
-                y: Some(odd),
-            })
+        Ok(Secp256k1Point::from_coords(fe_x, y))
+    }
+
+    /// Parse a raw 64 bytes public key `x || y`, i.e. an uncompressed
+    /// SEC without the `0x04` prefix, checking the point is on the curve
+    pub fn from_raw_xy(bytes: &[u8; 64]) -> Result<Self, String> {
+        let fe_x = coordinate(&bytes[..32])?;
+        let fe_y = coordinate(&bytes[32..])?;
+        if !Self::satisfies_curve(&fe_x, &fe_y) {
+            return Err("Point is not on the secp256k1 curve".to_string());
         }
+        Ok(Secp256k1Point::from_coords(fe_x, fe_y))
     }
 }
 
+/// Inverse of `n` modulo the group order, using Fermat's little theorem
+/// (`n^(order - 2) mod order`) since the order is prime
+pub fn mod_inverse_order(n: &BigUint) -> BigUint {
+    let order = Secp256k1::Order.as_biguint();
+    let exp = &order - BigUint::from(2u32);
+    n.modpow(&exp, &order)
+}
+
 impl Secp256k1 {
     pub fn as_point(&self) -> Secp256k1Point {
         match self {
             Secp256k1::Infinity => Secp256k1Point::new(None, None).unwrap(),
             Secp256k1::Generator => {
-                let x = FieldElement::new(
-                    "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
-                    PRIME,
+                let prime = shared_prime();
+                let x = FieldElement::from_biguint(
+                    BigUint::from_str_radix(
+                        "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                        16,
+                    )
+                    .unwrap(),
+                    &prime,
                 )
                 .unwrap();
-                let y = FieldElement::new(
-                    "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
-                    PRIME,
+                let y = FieldElement::from_biguint(
+                    BigUint::from_str_radix(
+                        "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+                        16,
+                    )
+                    .unwrap(),
+                    &prime,
                 )
                 .unwrap();
                 Secp256k1Point::new(Some(x), Some(y)).unwrap()
@@ -177,8 +497,8 @@ impl Secp256k1 {
 
     pub fn as_biguint(&self) -> BigUint {
         match self {
-            Secp256k1::Prime => BigUint::from_str_radix(PRIME, 16).unwrap(),
-            Secp256k1::Order => BigUint::from_str_radix(ORDER, 16).unwrap(),
+            Secp256k1::Prime => prime_biguint(),
+            Secp256k1::Order => order_biguint(),
             _ => panic!("Invalid enum as biguint"),
         }
     }
@@ -197,88 +517,28 @@ impl PartialEq for Secp256k1Point {
      * @returns bool
      */
     fn eq(&self, other: &Self) -> bool {
+        // Compare both coordinates structurally, so equality stays
+        // reflexive whatever coordinates are set
         self.x == other.x && self.y == other.y
     }
 }
 
+impl Eq for Secp256k1Point {}
+
+/// Hash consistently with `eq`, coordinate by coordinate
+impl Hash for Secp256k1Point {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
 // Implement Add trait to mimic __add__ in python
 impl Add for Secp256k1Point {
     type Output = Self;
 
     fn add(self, other: Secp256k1Point) -> Self {
-        // This is like P1 + 0 = P1
-        if self.x.is_none() {
-            return other;
-        }
-
-        // This is like 0 + P2 = P2
-        if other.x.is_none() {
-            return self;
-        }
-
-        // Tangent at y == 0 is Point at infinity
-        let zero = FieldElement::new("0", PRIME).unwrap();
-        if self == other && self.y.as_ref().unwrap() == &zero {
-            return Self { x: None, y: None };
-        }
-
-        if self.x == other.x {
-            if self.y != other.y {
-                // A point added to its negation is the point at infinity
-                return Self { x: None, y: None };
-            } else {
-                // Doubling algorithm
-                // Extract FieldElement references
-                let x1 = self.x.as_ref().unwrap();
-                let y1 = self.y.as_ref().unwrap();
-
-                // Compute slope: s = (y2 - y1) / (x2 - x1)
-                let two = FieldElement::new("2", PRIME).unwrap();
-                let three = FieldElement::new("3", PRIME).unwrap();
-                let numerator = &three * &x1.pow(&BigInt::from(2u32));
-                let denominator = &two * y1;
-                let s = &numerator / &denominator;
-
-                // Compute x3: x3 = s^2 - x1 - x2
-                let s2 = s.pow(&BigInt::from(2u32));
-                let x3 = &s2 - &(&two * x1);
-
-                // Compute y3: y3 = s * (x1 - x3) - y1
-                let x1_minus_x3 = x1 - &x3;
-                let y3 = &(&s * &x1_minus_x3) - y1;
-
-                return Self {
-                    x: Some(x3),
-                    y: Some(y3),
-                };
-            }
-        }
-
-        // Adding algorithm
-        // Extract FieldElement references
-        let x1 = self.x.as_ref().unwrap();
-        let y1 = self.y.as_ref().unwrap();
-        let x2 = other.x.as_ref().unwrap();
-        let y2 = other.y.as_ref().unwrap();
-
-        // Compute slope: s = (y2 - y1) / (x2 - x1)
-        let numerator = y2 - y1;
-        let denominator = x2 - x1;
-        let s = &numerator / &denominator;
-
-        // Compute x3: x3 = s^2 - x1 - x2
-        let s2 = s.pow(&BigInt::from(2u32));
-        let x3 = &(&s2 - x1) - x2;
-
-        // Compute y3: y3 = s * (x1 - x3) - y1
-        let x1_minus_x3 = x1 - &x3;
-        let y3 = &(&s * &x1_minus_x3) - y1;
-
-        // Return new point
-        Self {
-            x: Some(x3),
-            y: Some(y3),
-        }
+        &self + &other
     }
 }
 
@@ -286,6 +546,8 @@ impl<'b> Add<&'b Secp256k1Point> for &Secp256k1Point {
     type Output = Secp256k1Point;
 
     fn add(self, other: &'b Secp256k1Point) -> Secp256k1Point {
+        debug_assert!(self.infinity_check() && other.infinity_check());
+
         // This is like P1 + 0 = P1
         if self.x.is_none() {
             return other.clone();
@@ -296,41 +558,13 @@ impl<'b> Add<&'b Secp256k1Point> for &Secp256k1Point {
             return self.clone();
         }
 
-        // Tangent at y == 0 is Point at infinity
-        let zero = FieldElement::new("0", PRIME).unwrap();
-        if self == other && self.y.as_ref().unwrap() == &zero {
-            return Secp256k1Point { x: None, y: None };
-        }
-
         if self.x == other.x {
             if self.y != other.y {
                 // A point added to its negation is the point at infinity
-                return Secp256k1Point { x: None, y: None };
+                return Secp256k1Point::infinity();
             } else {
-                // Doubling algorithm
-                // Extract FieldElement references
-                let x1 = self.x.as_ref().unwrap();
-                let y1 = self.y.as_ref().unwrap();
-
-                // Compute slope: s = (3 * x1^2) / (2 * y1)
-                let two = FieldElement::new("2", PRIME).unwrap();
-                let three = FieldElement::new("3", PRIME).unwrap();
-                let numerator = &three * &x1.pow(&BigInt::from(2u32));
-                let denominator = &two * y1;
-                let s = &numerator / &denominator;
-
-                // Compute x3: x3 = s^2 - 2 * x1
-                let s2 = s.pow(&BigInt::from(2u32));
-                let x3 = &s2 - &(&two * x1);
-
-                // Compute y3: y3 = s * (x1 - x3) - y1
-                let x1_minus_x3 = x1 - &x3;
-                let y3 = &(&s * &x1_minus_x3) - y1;
-
-                return Secp256k1Point {
-                    x: Some(x3),
-                    y: Some(y3),
-                };
+                // Doubling algorithm, which also handles the tangent at y == 0
+                return self.double();
             }
         }
 
@@ -347,7 +581,7 @@ impl<'b> Add<&'b Secp256k1Point> for &Secp256k1Point {
         let s = &numerator / &denominator;
 
         // Compute x3: x3 = s^2 - x1 - x2
-        let s2 = s.pow(&BigInt::from(2u32));
+        let s2 = s.square();
         let x3 = &(&s2 - x1) - x2;
 
         // Compute y3: y3 = s * (x1 - x3) - y1
@@ -355,10 +589,36 @@ impl<'b> Add<&'b Secp256k1Point> for &Secp256k1Point {
         let y3 = &(&s * &x1_minus_x3) - y1;
 
         // Return new point
-        Secp256k1Point {
-            x: Some(x3),
-            y: Some(y3),
-        }
+        Secp256k1Point::from_coords(x3, y3)
+    }
+}
+
+/// Point subtraction, `P - Q = P + (-Q)`
+impl<'b> Sub<&'b Secp256k1Point> for &Secp256k1Point {
+    type Output = Secp256k1Point;
+
+    fn sub(self, other: &'b Secp256k1Point) -> Secp256k1Point {
+        Add::add(self, &other.negate())
+    }
+}
+
+/// Accumulate a point in place, as in `result += &current`
+impl AddAssign<&Secp256k1Point> for Secp256k1Point {
+    fn add_assign(&mut self, other: &Secp256k1Point) {
+        *self = &*self + other;
+    }
+}
+
+/// Sum points starting from the point at infinity, the group identity
+impl Sum for Secp256k1Point {
+    fn sum<I: Iterator<Item = Secp256k1Point>>(iter: I) -> Self {
+        iter.fold(Secp256k1Point::infinity(), |acc, p| &acc + &p)
+    }
+}
+
+impl<'a> Sum<&'a Secp256k1Point> for Secp256k1Point {
+    fn sum<I: Iterator<Item = &'a Secp256k1Point>>(iter: I) -> Self {
+        iter.fold(Secp256k1Point::infinity(), |acc, p| &acc + p)
     }
 }
 
@@ -366,18 +626,7 @@ impl Mul<BigUint> for Secp256k1Point {
     type Output = Secp256k1Point;
 
     fn mul(self, other: BigUint) -> Secp256k1Point {
-        let mut coef = other.clone();
-        let mut current = self.clone();
-        let mut result = Secp256k1Point::new(None, None).unwrap();
-
-        while coef > BigUint::zero() {
-            if &coef & BigUint::one() == BigUint::one() {
-                result = &result + &current;
-            }
-            current = &current + &current;
-            coef >>= 1;
-        }
-        result.clone()
+        scalar_mul(&self, &other)
     }
 }
 
@@ -385,18 +634,7 @@ impl Mul<Secp256k1Point> for BigUint {
     type Output = Secp256k1Point;
 
     fn mul(self, other: Secp256k1Point) -> Secp256k1Point {
-        let mut coef = self.clone();
-        let mut current = other.clone();
-        let mut result = Secp256k1Point::new(None, None).unwrap();
-
-        while coef > BigUint::zero() {
-            if &coef & BigUint::one() == BigUint::one() {
-                result = &result + &current;
-            }
-            current = &current + &current;
-            coef >>= 1;
-        }
-        result.clone()
+        scalar_mul(&other, &self)
     }
 }
 
@@ -404,18 +642,7 @@ impl Mul<&BigUint> for &Secp256k1Point {
     type Output = Secp256k1Point;
 
     fn mul(self, coefficient: &BigUint) -> Secp256k1Point {
-        let mut coef = coefficient.clone();
-        let mut current = self.clone();
-        let mut result = Secp256k1Point::new(None, None).unwrap();
-
-        while coef > BigUint::zero() {
-            if &coef & BigUint::one() == BigUint::one() {
-                result = &result + &current;
-            }
-            current = &current + &current;
-            coef >>= 1;
-        }
-        result.clone()
+        scalar_mul(self, coefficient)
     }
 }
 
@@ -423,18 +650,7 @@ impl Mul<&Secp256k1Point> for BigUint {
     type Output = Secp256k1Point;
 
     fn mul(self, other: &Secp256k1Point) -> Secp256k1Point {
-        let mut coef = self.clone();
-        let mut current = other.clone();
-        let mut result = Secp256k1Point::new(None, None).unwrap();
-
-        while coef > BigUint::zero() {
-            if &coef & BigUint::one() == BigUint::one() {
-                result = &result + &current;
-            }
-            current = &current + &current;
-            coef >>= 1;
-        }
-        result.clone()
+        scalar_mul(other, &self)
     }
 }
 
@@ -442,17 +658,161 @@ impl Mul<&Secp256k1Point> for &BigUint {
     type Output = Secp256k1Point;
 
     fn mul(self, other: &Secp256k1Point) -> Secp256k1Point {
-        let mut coef = self.clone();
-        let mut current = other.clone();
-        let mut result = Secp256k1Point::new(None, None).unwrap();
+        scalar_mul(other, self)
+    }
+}
+
+impl Mul<&BigUint> for Secp256k1Point {
+    type Output = Secp256k1Point;
+
+    fn mul(self, coefficient: &BigUint) -> Secp256k1Point {
+        scalar_mul(&self, coefficient)
+    }
+}
+
+impl Mul<BigUint> for &Secp256k1Point {
+    type Output = Secp256k1Point;
+
+    fn mul(self, coefficient: BigUint) -> Secp256k1Point {
+        scalar_mul(self, &coefficient)
+    }
+}
+
+impl Mul<Secp256k1Point> for &BigUint {
+    type Output = Secp256k1Point;
+
+    fn mul(self, other: Secp256k1Point) -> Secp256k1Point {
+        scalar_mul(&other, self)
+    }
+}
+
+impl Mul<Secp256k1Point> for u32 {
+    type Output = Secp256k1Point;
+
+    fn mul(self, other: Secp256k1Point) -> Secp256k1Point {
+        other.mul_u64(self as u64)
+    }
+}
+
+impl Mul<&Secp256k1Point> for u32 {
+    type Output = Secp256k1Point;
+
+    fn mul(self, other: &Secp256k1Point) -> Secp256k1Point {
+        other.mul_u64(self as u64)
+    }
+}
 
-        while coef > BigUint::zero() {
-            if &coef & BigUint::one() == BigUint::one() {
-                result = &result + &current;
+impl Mul<Secp256k1Point> for u64 {
+    type Output = Secp256k1Point;
+
+    fn mul(self, other: Secp256k1Point) -> Secp256k1Point {
+        other.mul_u64(self)
+    }
+}
+
+impl Mul<&Secp256k1Point> for u64 {
+    type Output = Secp256k1Point;
+
+    fn mul(self, other: &Secp256k1Point) -> Secp256k1Point {
+        other.mul_u64(self)
+    }
+}
+
+/// Compute the width-`window` non-adjacent form of `k`, from the least
+/// significant digit. Every non-zero digit is odd and lies in
+/// `(-2^(window-1), 2^(window-1))`
+fn wnaf(k: &BigUint, window: usize) -> Vec<i64> {
+    let modulus = 1i64 << window;
+    let half = modulus >> 1;
+    let mut k = k.clone();
+    let mut digits = Vec::new();
+
+    while !k.is_zero() {
+        if k.bit(0) {
+            let low = (&k % BigUint::from(modulus as u64)).to_u64().unwrap() as i64;
+            let digit = if low >= half { low - modulus } else { low };
+            if digit >= 0 {
+                k -= BigUint::from(digit as u64);
+            } else {
+                k += BigUint::from(digit.unsigned_abs());
             }
-            current = &current + &current;
-            coef >>= 1;
+            digits.push(digit);
+        } else {
+            digits.push(0);
+        }
+        k >>= 1;
+    }
+    digits
+}
+
+/// Scalar multiplication shared by all `Mul` implementations.
+///
+/// Every point in secp256k1 has the same order as the generator (cofactor 1),
+/// so `k` is first reduced modulo the order to skip useless iterations
+fn scalar_mul(point: &Secp256k1Point, k: &BigUint) -> Secp256k1Point {
+    let k = k % Secp256k1::Order.as_biguint();
+    double_and_add(point, &k)
+}
+
+/// Walk the bits of `k` from the least significant one, adding the current
+/// power-of-two multiple of `point` whenever the bit is set
+fn double_and_add(point: &Secp256k1Point, k: &BigUint) -> Secp256k1Point {
+    let mut current = point.clone();
+    let mut result = Secp256k1Point::infinity();
+
+    for i in 0..k.bits() {
+        if k.bit(i) {
+            result += &current;
         }
-        result.clone()
+        current = current.double();
+        debug_assert!(result.infinity_check() && current.infinity_check());
     }
+    result
+}
+
+/// The field prime, parsed once and cached
+#[cfg(feature = "std")]
+fn prime_biguint() -> BigUint {
+    static CACHE: std::sync::OnceLock<BigUint> = std::sync::OnceLock::new();
+    CACHE
+        .get_or_init(|| BigUint::from_str_radix(PRIME, 16).unwrap())
+        .clone()
+}
+
+#[cfg(not(feature = "std"))]
+fn prime_biguint() -> BigUint {
+    BigUint::from_str_radix(PRIME, 16).unwrap()
+}
+
+/// The field prime behind an `Arc`, built once and cached, so the
+/// coordinates built by this crate share one modulus
+#[cfg(feature = "std")]
+fn shared_prime() -> Arc<BigUint> {
+    static CACHE: std::sync::OnceLock<Arc<BigUint>> = std::sync::OnceLock::new();
+    CACHE.get_or_init(|| Arc::new(prime_biguint())).clone()
+}
+
+#[cfg(not(feature = "std"))]
+fn shared_prime() -> Arc<BigUint> {
+    Arc::new(prime_biguint())
+}
+
+/// Parse a big-endian coordinate into the secp256k1 field,
+/// checking it is below the prime
+fn coordinate(bytes: &[u8]) -> Result<FieldElement, String> {
+    FieldElement::from_biguint(BigUint::from_bytes_be(bytes), &shared_prime())
+}
+
+/// The group order, parsed once and cached
+#[cfg(feature = "std")]
+fn order_biguint() -> BigUint {
+    static CACHE: std::sync::OnceLock<BigUint> = std::sync::OnceLock::new();
+    CACHE
+        .get_or_init(|| BigUint::from_str_radix(ORDER, 16).unwrap())
+        .clone()
+}
+
+#[cfg(not(feature = "std"))]
+fn order_biguint() -> BigUint {
+    BigUint::from_str_radix(ORDER, 16).unwrap()
 }
@@ -1,10 +1,11 @@
 use field_element::FieldElement;
-use secp256k1::{Secp256k1, Secp256k1Point, PRIME};
+use secp256k1::{mod_inverse_order, Secp256k1, Secp256k1Point, ORDER, PRIME};
 
 #[cfg(test)]
 mod tests {
 
     use num_bigint::BigUint;
+    use num_traits::Num;
 
     use super::*;
 
@@ -337,4 +338,666 @@ mod tests {
         let deserialized_sec = Secp256k1Point::deserialize(compressed_sec).unwrap();
         assert_eq!(deserialized_sec, expected_p);
     }
+
+    #[test]
+    fn test_is_valid_public_key() {
+        let g = Secp256k1::Generator.as_point();
+        let three = BigUint::from(3u32);
+        let p = &g * &three;
+        assert!(g.is_valid_public_key());
+        assert!(p.is_valid_public_key());
+    }
+
+    #[test]
+    fn test_is_valid_public_key_fail_infinity() {
+        let p = Secp256k1::Infinity.as_point();
+        assert!(!p.is_valid_public_key());
+    }
+
+    #[test]
+    fn test_is_valid_public_key_fail_off_curve() {
+        // off-curve points cannot be built, so they never reach the check
+        let one = FieldElement::new("1", PRIME).unwrap();
+        assert!(Secp256k1Point::new(Some(one.clone()), Some(one)).is_err());
+    }
+
+    #[test]
+    fn test_is_valid_public_key_fail_other_field() {
+        // rejected without panicking on the mixed-field curve equation
+        let one = FieldElement::new("1", "d").unwrap();
+        assert!(Secp256k1Point::new(Some(one.clone()), Some(one)).is_err());
+    }
+
+    #[test]
+    fn test_mul_operator_forms_agree() {
+        let g = Secp256k1::Generator.as_point();
+        let k = BigUint::from(0xdeadbeefu32);
+
+        let expected = &g * &k;
+        assert_eq!(g.clone() * k.clone(), expected);
+        assert_eq!(k.clone() * g.clone(), expected);
+        assert_eq!(k.clone() * &g, expected);
+        assert_eq!(&k * &g, expected);
+    }
+
+    #[test]
+    fn test_mul_scalar_greater_than_order() {
+        let g = Secp256k1::Generator.as_point();
+        let five = BigUint::from(5u32);
+        let k = Secp256k1::Order.as_biguint() + &five;
+
+        assert_eq!(&g * &k, &g * &five);
+    }
+
+    #[test]
+    fn test_mul_ct_matches_mul() {
+        let g = Secp256k1::Generator.as_point();
+        let scalars = [
+            BigUint::from(1u32),
+            BigUint::from(2u32),
+            BigUint::from(5001u32),
+            BigUint::from(0xdeadbeef54321u64),
+            Secp256k1::Order.as_biguint() - BigUint::from(1u32),
+        ];
+
+        for k in scalars.iter() {
+            assert_eq!(g.mul_ct(k), &g * k);
+        }
+    }
+
+    #[test]
+    fn test_mul_ct_zero_is_infinity() {
+        let g = Secp256k1::Generator.as_point();
+        let i = Secp256k1::Infinity.as_point();
+        assert_eq!(g.mul_ct(&BigUint::from(0u32)), i);
+        assert_eq!(g.mul_ct(&Secp256k1::Order.as_biguint()), i);
+    }
+
+    #[test]
+    fn test_mul_ct_padding() {
+        let g = Secp256k1::Generator.as_point();
+        let order = Secp256k1::Order.as_biguint();
+        let two_256 = BigUint::from(1u32) << 256u32;
+        // k + n reaches 2^256 only above 2^256 - n, so these two
+        // scalars take the k + n and the k + 2n paddings
+        let high = &two_256 - &order + BigUint::from(7u32);
+        let low = &two_256 - &order - BigUint::from(7u32);
+        for k in [high, low, &order + BigUint::from(5u32)] {
+            assert_eq!(g.mul_ct(&k), &g * &k);
+        }
+        assert_eq!(
+            Secp256k1Point::infinity().mul_ct(&order),
+            Secp256k1Point::infinity()
+        );
+    }
+
+    #[test]
+    fn test_mul_small_int_double_g() {
+        let g = Secp256k1::Generator.as_point();
+        let x = FieldElement::new(
+            "C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5",
+            PRIME,
+        )
+        .unwrap();
+        let y = FieldElement::new(
+            "1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A",
+            PRIME,
+        )
+        .unwrap();
+        let p = Secp256k1Point::new(Some(x), Some(y)).unwrap();
+        assert_eq!(g.mul_u64(2), p);
+        assert_eq!(2u32 * &g, p);
+        assert_eq!(2u64 * g, p);
+    }
+
+    #[test]
+    fn test_mul_small_int_triple_g() {
+        let g = Secp256k1::Generator.as_point();
+        let x = FieldElement::new(
+            "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            PRIME,
+        )
+        .unwrap();
+        let y = FieldElement::new(
+            "388F7B0F632DE8140FE337E62A37F3566500A99934C2231B6CB9FD7584B8E672",
+            PRIME,
+        )
+        .unwrap();
+        let p = Secp256k1Point::new(Some(x), Some(y)).unwrap();
+        assert_eq!(g.mul_u64(3), p);
+        assert_eq!(3u64 * &g, p);
+        assert_eq!(3u32 * g, p);
+    }
+
+    #[test]
+    fn test_double() {
+        let g = Secp256k1::Generator.as_point();
+        let points = [g.clone(), g.mul_u64(3), g.mul_u64(5001)];
+
+        for p in points.iter() {
+            assert_eq!(p.double(), p + p);
+        }
+    }
+
+    #[test]
+    fn test_double_infinity() {
+        let i = Secp256k1::Infinity.as_point();
+        assert_eq!(i.double(), &i + &i);
+        assert_eq!(i.double(), i);
+    }
+
+    #[test]
+    fn test_double_y_zero_is_infinity() {
+        // A point with y = 0 would have order two, which the prime order
+        // group of secp256k1 has none of, so it cannot even be built
+        let p = Secp256k1Point::new(
+            Some(FieldElement::new("1", PRIME).unwrap()),
+            Some(FieldElement::new("0", PRIME).unwrap()),
+        );
+        assert!(p.is_err());
+    }
+
+    #[test]
+    fn test_eq_infinity_infinity() {
+        let p1 = Secp256k1::Infinity.as_point();
+        let p2 = Secp256k1Point::new(None, None).unwrap();
+        assert_eq!(p1, p2);
+    }
+
+    #[test]
+    fn test_eq_infinity_finite() {
+        let i = Secp256k1::Infinity.as_point();
+        let g = Secp256k1::Generator.as_point();
+        assert_ne!(i, g);
+        assert_ne!(g, i);
+    }
+
+    #[test]
+    fn test_eq_finite_finite() {
+        let g = Secp256k1::Generator.as_point();
+        assert_eq!(g, Secp256k1::Generator.as_point());
+        assert_ne!(g, g.double());
+    }
+
+    #[test]
+    fn test_new_partial_point() {
+        let g = Secp256k1::Generator.as_point();
+        assert!(Secp256k1Point::new(g.x().cloned(), None).is_err());
+        assert!(Secp256k1Point::new(None, g.y().cloned()).is_err());
+        assert_eq!(
+            Secp256k1Point::new(g.x().cloned(), g.y().cloned()).unwrap(),
+            g
+        );
+    }
+
+    #[test]
+    fn test_mul_wnaf_matches_mul() {
+        let g = Secp256k1::Generator.as_point();
+        let p = g.mul_u64(0xdeadbeef);
+        let scalars = [
+            BigUint::from(1u32),
+            BigUint::from(7u32),
+            BigUint::from(5001u32),
+            BigUint::from_str_radix("deadbeef54321deadbeef54321", 16).unwrap(),
+            BigUint::from_str_radix(
+                "7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d",
+                16,
+            )
+            .unwrap(),
+            Secp256k1::Order.as_biguint() - BigUint::from(1u32),
+        ];
+
+        for window in [2, 5] {
+            for k in scalars.iter() {
+                assert_eq!(g.mul_wnaf(k, window).unwrap(), &g * k);
+                assert_eq!(p.mul_wnaf(k, window).unwrap(), &p * k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_wnaf_zero_is_infinity() {
+        let g = Secp256k1::Generator.as_point();
+        let i = Secp256k1::Infinity.as_point();
+        assert_eq!(g.mul_wnaf(&BigUint::from(0u32), 4).unwrap(), i);
+        assert_eq!(i.mul_wnaf(&BigUint::from(5u32), 4).unwrap(), i);
+    }
+
+    #[test]
+    fn test_mul_wnaf_invalid_window() {
+        let g = Secp256k1::Generator.as_point();
+        let k = BigUint::from(5u32);
+        assert!(g.mul_wnaf(&k, 0).is_err());
+        assert!(g.mul_wnaf(&k, 1).is_err());
+        assert!(g.mul_wnaf(&k, 9).is_err());
+        assert_eq!(g.mul_wnaf(&k, 8).unwrap(), g.mul_u64(5));
+    }
+
+    #[test]
+    fn test_serialize_infinity_fail() {
+        let i = Secp256k1::Infinity.as_point();
+        assert!(i.serialize(true).is_err());
+        assert!(i.serialize(false).is_err());
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let g = Secp256k1::Generator.as_point();
+        // 153 * G has a x coordinate with a leading zero byte
+        for p in [g.mul_u64(3), g.mul_u64(153), g.mul_u64(5001)] {
+            let compressed = p.serialize(true).unwrap();
+            let uncompressed = p.serialize(false).unwrap();
+            assert_eq!(compressed.len(), 33);
+            assert_eq!(uncompressed.len(), 65);
+            assert_eq!(Secp256k1Point::deserialize(compressed).unwrap(), p);
+            assert_eq!(Secp256k1Point::deserialize(uncompressed).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn test_cached_constants_match_hex() {
+        let prime = BigUint::from_str_radix(PRIME, 16).unwrap();
+        let order = BigUint::from_str_radix(ORDER, 16).unwrap();
+        // call twice so the second read comes from the cache
+        for _ in 0..2 {
+            assert_eq!(Secp256k1::Prime.as_biguint(), prime);
+            assert_eq!(Secp256k1::Order.as_biguint(), order);
+        }
+    }
+
+    #[test]
+    fn test_mul_add() {
+        let g = Secp256k1::Generator.as_point();
+        let p = g.mul_u64(0xdeadbeef);
+        let u = BigUint::from_str_radix(
+            "bb1ab8f3c34c8e2d1d3bd45dc2f7c0d80d8dfe3d2cd2f1b7f0fa2f0fd3a0a5c1",
+            16,
+        )
+        .unwrap();
+        let v = BigUint::from(12345u32);
+        let expected = &(&u * &g) + &(&v * &p);
+        assert_eq!(Secp256k1Point::mul_add(&u, &g, &v, &p), expected);
+
+        // zero scalars drop their term
+        let zero = BigUint::from(0u32);
+        assert_eq!(Secp256k1Point::mul_add(&u, &g, &zero, &p), &u * &g);
+        assert_eq!(
+            Secp256k1Point::mul_add(&zero, &g, &zero, &p),
+            Secp256k1::Infinity.as_point()
+        );
+    }
+
+    #[test]
+    fn test_sum() {
+        let g = Secp256k1::Generator.as_point();
+        let points = vec![g.clone(), g.clone(), g.clone()];
+        assert_eq!(points.iter().sum::<Secp256k1Point>(), g.mul_u64(3));
+        assert_eq!(points.into_iter().sum::<Secp256k1Point>(), g.mul_u64(3));
+
+        let empty: Vec<Secp256k1Point> = vec![];
+        assert_eq!(
+            empty.into_iter().sum::<Secp256k1Point>(),
+            Secp256k1::Infinity.as_point()
+        );
+    }
+
+    #[test]
+    fn test_cached_generator() {
+        let g = Secp256k1Point::generator();
+        assert_eq!(*g, Secp256k1::Generator.as_point());
+        assert!(std::ptr::eq(g, Secp256k1Point::generator()));
+        // order * G == infinity, without reducing the scalar first
+        assert!(g.is_valid_public_key());
+    }
+
+    #[test]
+    fn test_from_raw_xy() {
+        let g = Secp256k1::Generator.as_point();
+        for p in [g.mul_u64(7), g.mul_u64(153)] {
+            let sec = p.to_uncompressed_sec().unwrap();
+            let raw: [u8; 64] = sec[1..].try_into().unwrap();
+            assert_eq!(Secp256k1Point::from_raw_xy(&raw).unwrap(), p);
+        }
+
+        // flip a bit of y so the point leaves the curve
+        let mut raw: [u8; 64] = g.to_uncompressed_sec().unwrap()[1..].try_into().unwrap();
+        raw[63] ^= 1;
+        assert!(Secp256k1Point::from_raw_xy(&raw).is_err());
+        assert!(Secp256k1Point::from_raw_xy(&[0xff; 64]).is_err());
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let g = Secp256k1::Generator.as_point();
+        let p = g.mul_u64(42);
+
+        let mut acc = g.clone();
+        acc += &p;
+        assert_eq!(acc, &g + &p);
+
+        // adding infinity keeps the point
+        let mut acc = p.clone();
+        acc += &Secp256k1::Infinity.as_point();
+        assert_eq!(acc, p);
+    }
+
+    #[test]
+    fn test_mod_inverse_order() {
+        let order = Secp256k1::Order.as_biguint();
+        for n in [
+            BigUint::from(1u32),
+            BigUint::from(2u32),
+            BigUint::from(0xdeadbeefu32),
+            &order - BigUint::from(1u32),
+        ] {
+            let inv = mod_inverse_order(&n);
+            assert_eq!((&n * inv) % &order, BigUint::from(1u32));
+        }
+    }
+
+    #[test]
+    fn test_mul_all_combinations() {
+        let g = Secp256k1::Generator.as_point();
+        let k = BigUint::from(7u32);
+        let expected = g.mul_u64(7);
+
+        // point * scalar
+        assert_eq!(g.clone() * k.clone(), expected);
+        assert_eq!(g.clone() * &k, expected);
+        assert_eq!(&g * k.clone(), expected);
+        assert_eq!(&g * &k, expected);
+
+        // scalar * point
+        assert_eq!(k.clone() * g.clone(), expected);
+        assert_eq!(k.clone() * &g, expected);
+        assert_eq!(&k * g.clone(), expected);
+        assert_eq!(&k * &g, expected);
+    }
+
+    #[test]
+    fn test_is_on_curve() {
+        let g = Secp256k1::Generator.as_point();
+        assert!(g.is_on_curve());
+        assert!(g.mul_u64(153).is_on_curve());
+        assert!(Secp256k1::Infinity.as_point().is_on_curve());
+
+        // y + 1 is not on the curve
+        let y = g.y().unwrap();
+        let off_curve = Secp256k1Point::new(
+            g.x().cloned(),
+            Some(
+                FieldElement::from_biguint(&y.num + BigUint::from(1u32), y.shared_prime()).unwrap(),
+            ),
+        );
+        assert!(off_curve.is_err());
+    }
+
+    #[test]
+    fn test_hash_set_dedup() {
+        let g = Secp256k1::Generator.as_point();
+        let mut set = std::collections::HashSet::new();
+        set.insert(g.clone());
+        set.insert(g.mul_u64(2));
+        set.insert(Secp256k1::Generator.as_point());
+        assert_eq!(set.len(), 2);
+
+        set.insert(Secp256k1::Infinity.as_point());
+        set.insert(Secp256k1::Infinity.as_point());
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_combine() {
+        let g = Secp256k1::Generator.as_point();
+        let order = Secp256k1::Order.as_biguint();
+        let a = &order - BigUint::from(5u32);
+        let b = BigUint::from(12345u32);
+
+        let combined = Secp256k1Point::combine(&[&a * &g, &b * &g]).unwrap();
+        assert_eq!(combined, ((&a + &b) % &order) * &g);
+    }
+
+    #[test]
+    fn test_combine_fail_infinity() {
+        let g = Secp256k1::Generator.as_point();
+        let order = Secp256k1::Order.as_biguint();
+        let minus_one = &order - BigUint::from(1u32);
+        assert!(Secp256k1Point::combine(&[g.clone(), &minus_one * &g]).is_err());
+        assert!(Secp256k1Point::combine(&[]).is_err());
+    }
+
+    #[test]
+    fn test_y_is_even() {
+        // G's y coordinate ends with 0xb8, so -G = (order - 1) * G has an odd one
+        let g = Secp256k1::Generator.as_point();
+        let minus_g = (Secp256k1::Order.as_biguint() - BigUint::from(1u32)) * &g;
+        assert_eq!(g.y_is_even(), Some(true));
+        assert_eq!(minus_g.y_is_even(), Some(false));
+        assert_eq!(Secp256k1::Infinity.as_point().y_is_even(), None);
+
+        // consistent with the compressed SEC prefix
+        for p in [g.mul_u64(2), g.mul_u64(7), minus_g] {
+            let expected = p.to_compressed_sec().unwrap()[0] == 2;
+            assert_eq!(p.y_is_even(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_to_p2pkh_address() {
+        let p = Secp256k1::Generator.as_point().mul_u64(3);
+        assert_eq!(
+            p.to_p2pkh_address(true, false).unwrap(),
+            "1CUNEBjYrCn2y1SdiUMohaKUi4wpP326Lb"
+        );
+        assert_eq!(
+            p.to_p2pkh_address(true, true).unwrap(),
+            "mrzKXEpXfEDHk7vFS3LBXVXoa4YXFcCkje"
+        );
+        assert!(Secp256k1::Infinity
+            .as_point()
+            .to_p2pkh_address(true, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_infinity_check() {
+        let g = Secp256k1::Generator.as_point();
+        assert!(g.infinity_check());
+        assert!(Secp256k1Point::infinity().infinity_check());
+        assert_eq!(Secp256k1Point::infinity(), Secp256k1::Infinity.as_point());
+        assert!(Secp256k1Point::new(g.x().cloned(), None).is_err());
+    }
+
+    #[test]
+    fn test_mul_intermediate_points_infinity_check() {
+        // Each multiplication debug_asserts `infinity_check` on its
+        // intermediate points, including scalars around the order where
+        // the result wraps around to the point at infinity
+        let g = Secp256k1::Generator.as_point();
+        let order = BigUint::from_str_radix(ORDER, 16).unwrap();
+        let one = BigUint::from(1u32);
+        let scalars = [
+            (BigUint::from(0u32), true),
+            (one.clone(), false),
+            (&order - &one, false),
+            (order.clone(), true),
+            (&order + &one, false),
+            (&order * 2u32, true),
+        ];
+
+        for (k, is_infinity) in scalars.iter() {
+            let results = [
+                &g * k,
+                g.mul_ct(k),
+                g.mul_wnaf(k, 4).unwrap(),
+                Secp256k1Point::mul_add(k, &g, &one, &Secp256k1Point::infinity()),
+            ];
+            for result in results.iter() {
+                assert!(result.infinity_check());
+                assert_eq!(result.x().is_none(), *is_infinity);
+                assert_eq!(result, &results[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deserialize_hybrid_sec() {
+        let g = Secp256k1::Generator.as_point();
+        // G has an even y, so -G = (order - 1) * G has an odd one
+        let order = BigUint::from_str_radix(ORDER, 16).unwrap();
+        let neg_g = &g * &(order - BigUint::from(1u32));
+        for (p, prefix, wrong) in [(&g, 6u8, 7u8), (&neg_g, 7u8, 6u8)] {
+            let mut hybrid = p.serialize(false).unwrap();
+            hybrid[0] = prefix;
+            assert_eq!(Secp256k1Point::deserialize(hybrid.clone()).unwrap(), *p);
+
+            // prefix says the opposite parity of y
+            hybrid[0] = wrong;
+            assert!(Secp256k1Point::deserialize(hybrid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_unknown_prefix() {
+        let g = Secp256k1::Generator.as_point();
+        for prefix in [0u8, 1u8, 5u8, 8u8, 0xffu8] {
+            let mut sec = g.serialize(false).unwrap();
+            sec[0] = prefix;
+            assert!(Secp256k1Point::deserialize(sec).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_wrong_length() {
+        let g = Secp256k1::Generator.as_point();
+        let compressed = g.serialize(true).unwrap();
+        let uncompressed = g.serialize(false).unwrap();
+        let mut hybrid = uncompressed.clone();
+        hybrid[0] = 6;
+        assert!(Secp256k1Point::deserialize(hybrid.clone()).is_ok());
+
+        for sec in [compressed, uncompressed, hybrid] {
+            // trailing garbage
+            let mut longer = sec.clone();
+            longer.push(0);
+            assert!(Secp256k1Point::deserialize(longer).is_err());
+
+            // truncated
+            let shorter = sec[..sec.len() - 1].to_vec();
+            assert!(Secp256k1Point::deserialize(shorter).is_err());
+        }
+
+        // a compressed prefix in front of a whole uncompressed SEC
+        let mut sec = g.serialize(false).unwrap();
+        sec[0] = 2;
+        assert!(Secp256k1Point::deserialize(sec).is_err());
+        assert!(Secp256k1Point::deserialize(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_compressed_not_on_curve() {
+        // 5^3 + 7 has no square root mod the prime
+        let mut sec = vec![0u8; 33];
+        sec[0] = 2;
+        sec[32] = 5;
+        assert!(Secp256k1Point::deserialize(sec.clone()).is_err());
+        sec[0] = 3;
+        assert!(Secp256k1Point::deserialize(sec).is_err());
+
+        let mut x = [0u8; 32];
+        x[31] = 5;
+        assert!(Secp256k1Point::lift_x(&x).is_err());
+    }
+
+    #[test]
+    fn test_mul_large_scalar_shares_prime() {
+        // Guards the shared prime of FieldElement: a full 256 bits scalar
+        // multiplication must still give the expected point
+        let g = Secp256k1::Generator.as_point();
+        let k = BigUint::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bad9e08143f8a7352",
+            16,
+        )
+        .unwrap();
+        let expected = Secp256k1Point::new(
+            Some(
+                FieldElement::new(
+                    "f973a0b87062c389d125d8199e803b832b6ac6bf7867a4f6cd87506060fc4c58",
+                    PRIME,
+                )
+                .unwrap(),
+            ),
+            Some(
+                FieldElement::new(
+                    "b4b5f5c0d936773ab3dc94ddb3b7449fa4d9a6b619a3f67132785a7d35ef19d2",
+                    PRIME,
+                )
+                .unwrap(),
+            ),
+        )
+        .unwrap();
+
+        let p = &g * &k;
+        assert_eq!(p, expected);
+        assert_eq!(g.mul_ct(&k), expected);
+        assert_eq!(g.mul_wnaf(&k, 5).unwrap(), expected);
+
+        // the results share the modulus of the generator
+        let g_prime = g.x().unwrap().shared_prime();
+        assert!(std::sync::Arc::ptr_eq(
+            p.x().unwrap().shared_prime(),
+            g_prime
+        ));
+        assert!(std::sync::Arc::ptr_eq(
+            p.y().unwrap().shared_prime(),
+            g_prime
+        ));
+    }
+
+    #[test]
+    fn test_negate() {
+        let g = Secp256k1::Generator.as_point();
+        let i = Secp256k1Point::infinity();
+        for p in [g.clone(), g.mul_u64(3), g.mul_u64(0xdeadbeef)] {
+            let neg = p.negate();
+            assert!(neg.is_on_curve());
+            assert_eq!(neg.x(), p.x());
+            assert_ne!(neg, p);
+            assert_eq!(&p + &neg, i);
+            assert_eq!(neg.negate(), p);
+        }
+
+        // -G = (order - 1) * G
+        let order = BigUint::from_str_radix(ORDER, 16).unwrap();
+        assert_eq!(g.negate(), &g * &(order - BigUint::from(1u32)));
+        assert_eq!(i.negate(), i);
+    }
+
+    #[test]
+    fn test_sub() {
+        let g = Secp256k1::Generator.as_point();
+        let i = Secp256k1Point::infinity();
+        let p = g.mul_u64(5);
+        assert_eq!(&p - &p, i);
+        assert_eq!(&p - &g, g.mul_u64(4));
+        assert_eq!(&g - &p, g.mul_u64(4).negate());
+        assert_eq!(&p - &i, p);
+        assert_eq!(&i - &p, p.negate());
+    }
+
+    #[test]
+    fn test_deserialize_tampered_uncompressed_sec() {
+        let g = Secp256k1::Generator.as_point();
+        let mut sec = g.serialize(false).unwrap();
+        sec[64] ^= 1;
+        assert!(Secp256k1Point::deserialize(sec.clone()).is_err());
+
+        // the same tampered point with a hybrid prefix of matching parity
+        sec[0] = 7;
+        assert!(Secp256k1Point::deserialize(sec).is_err());
+
+        let mut sec = g.serialize(false).unwrap();
+        sec[1] ^= 1;
+        assert!(Secp256k1Point::deserialize(sec).is_err());
+    }
 }